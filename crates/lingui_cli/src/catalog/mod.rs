@@ -0,0 +1,259 @@
+use lingui_macro::LinguiOptions;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+pub mod po;
+pub mod xliff;
+
+/// Source location of a message, serialized as `[file, line]` like in Lingui catalogs.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct Origin(pub String, pub usize);
+
+impl Origin {
+    /// Parse a `file:line` reference, a missing line number is stored as 0
+    pub fn parse(reference: &str) -> Origin {
+        match reference.rsplit_once(':') {
+            Some((file, line)) if !line.is_empty() && line.bytes().all(|b| b.is_ascii_digit()) => {
+                Origin(file.into(), line.parse().unwrap_or_default())
+            }
+            _ => Origin(reference.into(), 0),
+        }
+    }
+}
+
+impl std::fmt::Display for Origin {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.0, self.1)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CatalogMessage {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub context: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub comments: Vec<String>,
+    #[serde(default, rename = "origin", skip_serializing_if = "Vec::is_empty")]
+    pub origins: Vec<Origin>,
+    /// gettext flags such as `fuzzy`, kept with the translation
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub flags: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub translation: Option<String>,
+    /// no longer found in the sources, kept so translations are not lost
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub obsolete: bool,
+
+    /// value placeholder names known from extraction, not persisted
+    #[serde(skip)]
+    pub placeholders: Vec<String>,
+    /// component placeholder names known from extraction, not persisted
+    #[serde(skip)]
+    pub components: Vec<String>,
+}
+
+/// Messages keyed by id, kept sorted so the output is stable between runs.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Catalog {
+    pub messages: BTreeMap<String, CatalogMessage>,
+}
+
+impl Catalog {
+    /// Add a message to the catalog. Occurrences of the same id are merged:
+    /// origins, comments and flags accumulate, the first seen message wins.
+    pub fn add(&mut self, id: String, message: CatalogMessage) {
+        let entry = self.messages.entry(id).or_default();
+
+        if entry.message.is_none() {
+            entry.message = message.message;
+        }
+        if entry.context.is_none() {
+            entry.context = message.context;
+        }
+        if entry.translation.is_none() {
+            entry.translation = message.translation;
+        }
+        if entry.placeholders.is_empty() {
+            entry.placeholders = message.placeholders;
+        }
+        if entry.components.is_empty() {
+            entry.components = message.components;
+        }
+
+        for comment in message.comments {
+            if !entry.comments.contains(&comment) {
+                entry.comments.push(comment);
+            }
+        }
+
+        for origin in message.origins {
+            if !entry.origins.contains(&origin) {
+                entry.origins.push(origin);
+            }
+        }
+
+        for flag in message.flags {
+            if !entry.flags.contains(&flag) {
+                entry.flags.push(flag);
+            }
+        }
+    }
+
+    /// Carry translations over from a previously written catalog.
+    /// Messages which are not part of this catalog anymore are kept as obsolete.
+    pub fn merge_previous(&mut self, previous: Catalog) {
+        for (id, prev) in previous.messages {
+            match self.messages.get_mut(&id) {
+                Some(message) => {
+                    message.translation = prev.translation;
+                    message.flags = prev.flags;
+                }
+                None => {
+                    self.messages.insert(
+                        id,
+                        CatalogMessage {
+                            obsolete: true,
+                            ..prev
+                        },
+                    );
+                }
+            }
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("catalog is always serializable")
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum CatalogFormat {
+    /// Lingui JSON catalog
+    Json,
+    /// Gettext PO
+    Po,
+    /// XLIFF 2.0
+    Xliff,
+}
+
+impl CatalogFormat {
+    /// Guess the format from a file extension, defaults to JSON
+    pub fn from_path(path: &Path) -> CatalogFormat {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("po") => CatalogFormat::Po,
+            Some("xlf" | "xliff") => CatalogFormat::Xliff,
+            _ => CatalogFormat::Json,
+        }
+    }
+
+    pub fn serialize(
+        &self,
+        catalog: &Catalog,
+        source_locale: &str,
+        locale: Option<&str>,
+        options: &LinguiOptions,
+    ) -> String {
+        match self {
+            CatalogFormat::Json => catalog.to_json(),
+            CatalogFormat::Po => po::serialize(catalog, locale, options),
+            CatalogFormat::Xliff => xliff::serialize(catalog, source_locale, locale),
+        }
+    }
+
+    pub fn parse(
+        &self,
+        source: &str,
+        options: &LinguiOptions,
+    ) -> Result<Catalog, Box<dyn std::error::Error>> {
+        Ok(match self {
+            CatalogFormat::Json => serde_json::from_str(source)?,
+            CatalogFormat::Po => po::parse(source, options)?,
+            CatalogFormat::Xliff => xliff::parse(source)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(text: &str, origin: Origin) -> CatalogMessage {
+        CatalogMessage {
+            message: Some(text.into()),
+            origins: vec![origin],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn merges_origins_of_the_same_id() {
+        let mut catalog = Catalog::default();
+        catalog.add("a".into(), message("Hello", Origin("a.tsx".into(), 1)));
+        catalog.add("a".into(), message("Hello", Origin("b.tsx".into(), 4)));
+        catalog.add("a".into(), message("Hello", Origin("b.tsx".into(), 4)));
+
+        assert_eq!(
+            catalog.messages["a"].origins,
+            vec![Origin("a.tsx".into(), 1), Origin("b.tsx".into(), 4)]
+        );
+    }
+
+    #[test]
+    fn keeps_translations_and_obsolete_messages() {
+        let mut previous = Catalog::default();
+        previous.add("a".into(), message("Hello", Origin("a.tsx".into(), 1)));
+        previous.add("b".into(), message("Removed", Origin("a.tsx".into(), 2)));
+        previous.messages.get_mut("a").unwrap().translation = Some("Ahoj".into());
+        previous.messages.get_mut("a").unwrap().flags = vec!["fuzzy".into()];
+
+        let mut catalog = Catalog::default();
+        catalog.add("a".into(), message("Hello", Origin("a.tsx".into(), 5)));
+        catalog.merge_previous(previous);
+
+        assert_eq!(catalog.messages["a"].translation.as_deref(), Some("Ahoj"));
+        assert_eq!(catalog.messages["a"].flags, vec!["fuzzy"]);
+        assert_eq!(
+            catalog.messages["a"].origins,
+            vec![Origin("a.tsx".into(), 5)]
+        );
+        assert!(catalog.messages["b"].obsolete);
+    }
+
+    #[test]
+    fn serializes_to_lingui_json_format() {
+        let mut catalog = Catalog::default();
+        catalog.add(
+            "vQhkQx".into(),
+            CatalogMessage {
+                message: Some("my message".into()),
+                comments: vec!["note".into()],
+                origins: vec![Origin("src/App.tsx".into(), 3)],
+                placeholders: vec!["name".into()],
+                ..Default::default()
+            },
+        );
+
+        assert_eq!(
+            catalog.to_json(),
+            r#"{
+  "vQhkQx": {
+    "message": "my message",
+    "comments": [
+      "note"
+    ],
+    "origin": [
+      [
+        "src/App.tsx",
+        3
+      ]
+    ]
+  }
+}"#
+        );
+    }
+}
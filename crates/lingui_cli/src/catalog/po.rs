@@ -0,0 +1,681 @@
+//! Gettext PO serialization compatible with `@lingui/format-po`.
+//!
+//! Messages with a generated id are written with the source message as `msgid`;
+//! the id is recomputed with `LinguiOptions::generate_message_id` when the file is read back.
+//! Messages with an explicit id use it as `msgid` and are marked with the
+//! `#, js-lingui-explicit-id` flag, their source message is kept in a
+//! `js-lingui-message` comment so nothing is lost on a round-trip.
+//! Each `#.` line is read as a separate comment, like `@lingui/format-po` does.
+
+use super::{Catalog, CatalogMessage, Origin};
+use lingui_macro::LinguiOptions;
+use std::fmt::Write;
+
+const EXPLICIT_ID_FLAG: &str = "js-lingui-explicit-id";
+const LINGUI_ID_PREFIX: &str = "js-lingui-id: ";
+const MESSAGE_PREFIX: &str = "js-lingui-message: ";
+
+#[derive(Debug, PartialEq)]
+pub struct ParseError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+fn is_generated_id(id: &str, message: &CatalogMessage, options: &LinguiOptions) -> bool {
+    message.message.as_deref().is_some_and(|msg| {
+        options.generate_message_id(msg, message.context.as_deref().unwrap_or_default()) == id
+    })
+}
+
+fn escape(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            '"' => out.push_str("\\\""),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+fn unescape(value: &str) -> Result<String, String> {
+    let mut out = String::with_capacity(value.len());
+    let mut chars = value.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+
+        match chars.next() {
+            Some('n') => out.push('\n'),
+            Some('r') => out.push('\r'),
+            Some('t') => out.push('\t'),
+            Some('\\') => out.push('\\'),
+            Some('"') => out.push('"'),
+            Some(other) => return Err(format!("unknown escape sequence `\\{other}`")),
+            None => return Err("unterminated escape sequence".into()),
+        }
+    }
+
+    Ok(out)
+}
+
+/// Parse a quoted PO string `"..."` into its unescaped value
+fn parse_quoted(value: &str) -> Result<String, String> {
+    let value = value.trim();
+    let inner = value
+        .strip_prefix('"')
+        .and_then(|v| v.strip_suffix('"'))
+        .filter(|_| value.len() >= 2)
+        .ok_or_else(|| format!("expected a quoted string, found `{value}`"))?;
+
+    unescape(inner)
+}
+
+fn write_keyword(out: &mut String, prefix: &str, keyword: &str, value: &str) {
+    let lines: Vec<&str> = value.split_inclusive('\n').collect();
+
+    if lines.len() > 1 {
+        let _ = writeln!(out, "{prefix}{keyword} \"\"");
+        for line in lines {
+            let _ = writeln!(out, "{prefix}\"{}\"", escape(line));
+        }
+    } else {
+        let _ = writeln!(out, "{prefix}{keyword} \"{}\"", escape(value));
+    }
+}
+
+fn write_entry(out: &mut String, id: &str, message: &CatalogMessage, options: &LinguiOptions) {
+    for comment in &message.comments {
+        for line in comment.lines() {
+            let _ = writeln!(out, "#. {line}");
+        }
+    }
+
+    let generated = is_generated_id(id, message, options);
+
+    if !generated {
+        if let Some(msg) = &message.message {
+            let _ = writeln!(out, "#. {MESSAGE_PREFIX}\"{}\"", escape(msg));
+        }
+    }
+
+    for origin in &message.origins {
+        let _ = writeln!(out, "#: {origin}");
+    }
+
+    let flags: Vec<&str> = (!generated)
+        .then_some(EXPLICIT_ID_FLAG)
+        .into_iter()
+        .chain(message.flags.iter().map(String::as_str))
+        .collect();
+    if !flags.is_empty() {
+        let _ = writeln!(out, "#, {}", flags.join(", "));
+    }
+
+    let prefix = if message.obsolete { "#~ " } else { "" };
+
+    if let Some(context) = &message.context {
+        write_keyword(out, prefix, "msgctxt", context);
+    }
+
+    let msgid = if generated {
+        message.message.as_deref().unwrap_or(id)
+    } else {
+        id
+    };
+
+    write_keyword(out, prefix, "msgid", msgid);
+    write_keyword(
+        out,
+        prefix,
+        "msgstr",
+        message.translation.as_deref().unwrap_or_default(),
+    );
+}
+
+pub fn serialize(catalog: &Catalog, locale: Option<&str>, options: &LinguiOptions) -> String {
+    let mut out = String::new();
+
+    out.push_str("msgid \"\"\nmsgstr \"\"\n");
+    out.push_str("\"MIME-Version: 1.0\\n\"\n");
+    out.push_str("\"Content-Type: text/plain; charset=utf-8\\n\"\n");
+    out.push_str("\"Content-Transfer-Encoding: 8bit\\n\"\n");
+    out.push_str("\"X-Generator: lingui-swc\\n\"\n");
+    if let Some(locale) = locale {
+        let _ = writeln!(out, "\"Language: {}\\n\"", escape(locale));
+    }
+
+    for (id, message) in &catalog.messages {
+        out.push('\n');
+        write_entry(&mut out, id, message, options);
+    }
+
+    out
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Field {
+    Context,
+    Id,
+    Str,
+}
+
+#[derive(Default)]
+struct Entry {
+    comments: Vec<String>,
+    origins: Vec<Origin>,
+    flags: Vec<String>,
+    context: Option<String>,
+    msgid: Option<String>,
+    msgstr: Option<String>,
+    obsolete: bool,
+}
+
+impl Entry {
+    fn is_empty(&self) -> bool {
+        self.comments.is_empty()
+            && self.origins.is_empty()
+            && self.flags.is_empty()
+            && self.context.is_none()
+            && self.msgid.is_none()
+    }
+
+    fn field_mut(&mut self, field: Field) -> &mut Option<String> {
+        match field {
+            Field::Context => &mut self.context,
+            Field::Id => &mut self.msgid,
+            Field::Str => &mut self.msgstr,
+        }
+    }
+
+    fn finish(
+        self,
+        catalog: &mut Catalog,
+        line: usize,
+        options: &LinguiOptions,
+    ) -> Result<(), ParseError> {
+        let Some(msgid) = self.msgid else {
+            if self.is_empty() {
+                return Ok(());
+            }
+            return Err(ParseError {
+                line,
+                message: "entry is missing `msgid`".into(),
+            });
+        };
+
+        // header entry
+        if msgid.is_empty() && self.context.is_none() {
+            return Ok(());
+        }
+
+        let mut flags = self.flags;
+        // older catalogs wrote the flag as a `#.` comment
+        let mut explicit = flags.iter().any(|flag| flag == EXPLICIT_ID_FLAG);
+        flags.retain(|flag| flag != EXPLICIT_ID_FLAG);
+        let mut lingui_id: Option<String> = None;
+        let mut source_message: Option<String> = None;
+        let mut comments = Vec::new();
+
+        for comment in self.comments {
+            if comment == EXPLICIT_ID_FLAG {
+                explicit = true;
+            } else if let Some(id) = comment.strip_prefix(LINGUI_ID_PREFIX) {
+                lingui_id = Some(id.trim().into());
+            } else if let Some(msg) = comment.strip_prefix(MESSAGE_PREFIX) {
+                source_message =
+                    Some(parse_quoted(msg).map_err(|message| ParseError { line, message })?);
+            } else {
+                comments.push(comment);
+            }
+        }
+
+        let (id, message) = if explicit {
+            (msgid, source_message)
+        } else {
+            let id = lingui_id.unwrap_or_else(|| {
+                options.generate_message_id(&msgid, self.context.as_deref().unwrap_or_default())
+            });
+            (id, Some(msgid))
+        };
+
+        catalog.messages.insert(
+            id,
+            CatalogMessage {
+                message,
+                context: self.context,
+                comments,
+                origins: self.origins,
+                flags,
+                translation: self.msgstr.filter(|t| !t.is_empty()),
+                obsolete: self.obsolete,
+                ..Default::default()
+            },
+        );
+
+        Ok(())
+    }
+}
+
+/// Split a `#:` line into origins. References are separated by whitespace, but
+/// file names may contain spaces too, so words are joined up to the next one
+/// ending with a `:line` suffix.
+fn parse_references(references: &str) -> Vec<Origin> {
+    let mut origins = Vec::new();
+    let mut reference = String::new();
+
+    for word in references.split_whitespace() {
+        if !reference.is_empty() {
+            reference.push(' ');
+        }
+        reference.push_str(word);
+
+        if Origin::parse(word).1 != 0 {
+            origins.push(Origin::parse(&std::mem::take(&mut reference)));
+        }
+    }
+
+    if !reference.is_empty() {
+        origins.push(Origin::parse(&reference));
+    }
+
+    origins
+}
+
+pub fn parse(source: &str, options: &LinguiOptions) -> Result<Catalog, ParseError> {
+    let mut catalog = Catalog::default();
+    let mut entry = Entry::default();
+    let mut current: Option<Field> = None;
+
+    for (index, raw_line) in source.lines().enumerate() {
+        let line_no = index + 1;
+        let error = |message: String| ParseError {
+            line: line_no,
+            message,
+        };
+        let mut line = raw_line.trim();
+
+        if line.is_empty() {
+            std::mem::take(&mut entry).finish(&mut catalog, line_no, options)?;
+            current = None;
+            continue;
+        }
+
+        if let Some(rest) = line.strip_prefix("#~") {
+            entry.obsolete = true;
+            line = rest.trim_start();
+        } else if line.starts_with('#') {
+            // comments start the next entry, even without a separating blank line
+            if entry.msgstr.is_some() {
+                std::mem::take(&mut entry).finish(&mut catalog, line_no, options)?;
+                current = None;
+            }
+
+            if let Some(comment) = line.strip_prefix("#.") {
+                // only the separating space is dropped to keep indented lines
+                entry
+                    .comments
+                    .push(comment.strip_prefix(' ').unwrap_or(comment).into());
+            } else if let Some(references) = line.strip_prefix("#:") {
+                entry.origins.extend(parse_references(references));
+            } else if let Some(flags) = line.strip_prefix("#,") {
+                entry.flags.extend(
+                    flags
+                        .split(',')
+                        .map(str::trim)
+                        .filter(|flag| !flag.is_empty())
+                        .map(String::from),
+                );
+            }
+            // translator comments and previous strings are not preserved
+            continue;
+        }
+
+        if line.starts_with('"') {
+            let Some(field) = current else {
+                return Err(error("string continuation without a keyword".into()));
+            };
+            let value = parse_quoted(line).map_err(error)?;
+            entry
+                .field_mut(field)
+                .get_or_insert_with(String::new)
+                .push_str(&value);
+            continue;
+        }
+
+        let (keyword, value) = line
+            .split_once(char::is_whitespace)
+            .ok_or_else(|| error(format!("unexpected line `{line}`")))?;
+
+        let field = match keyword {
+            "msgctxt" => Field::Context,
+            "msgid" => Field::Id,
+            "msgstr" => Field::Str,
+            "msgid_plural" => {
+                return Err(error(
+                    "gettext plurals are not supported, Lingui messages use ICU plurals".into(),
+                ))
+            }
+            _ => return Err(error(format!("unknown keyword `{keyword}`"))),
+        };
+
+        // a new entry may start without a separating blank line
+        if field != Field::Str && entry.msgstr.is_some() {
+            let obsolete = entry.obsolete;
+            std::mem::take(&mut entry).finish(&mut catalog, line_no, options)?;
+            entry.obsolete = obsolete;
+        }
+
+        *entry.field_mut(field) = Some(parse_quoted(value).map_err(error)?);
+        current = Some(field);
+    }
+
+    entry.finish(&mut catalog, source.lines().count(), options)?;
+
+    Ok(catalog)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lingui_macro::{generate_message_id, IdGeneration, IdStrategy};
+
+    fn sample_catalog() -> Catalog {
+        let mut catalog = Catalog::default();
+
+        for (message, context) in [
+            ("Hello {name}", None),
+            ("Save", Some("toolbar")),
+            ("Multiline\nmessage with \"quotes\"", None),
+        ] {
+            let id = generate_message_id(message, context.unwrap_or_default(), false);
+            catalog.add(
+                id,
+                CatalogMessage {
+                    message: Some(message.into()),
+                    context: context.map(Into::into),
+                    comments: vec!["translator note".into()],
+                    origins: vec![Origin("src/App.tsx".into(), 4)],
+                    ..Default::default()
+                },
+            );
+        }
+
+        catalog.add(
+            "nav.home".into(),
+            CatalogMessage {
+                message: Some("Home".into()),
+                origins: vec![
+                    Origin("src/Nav.tsx".into(), 1),
+                    Origin("src/Footer.tsx".into(), 9),
+                ],
+                translation: Some("Domů".into()),
+                ..Default::default()
+            },
+        );
+
+        catalog.messages.insert(
+            "removed".into(),
+            CatalogMessage {
+                translation: Some("Odstraněno".into()),
+                obsolete: true,
+                ..Default::default()
+            },
+        );
+
+        catalog
+    }
+
+    #[test]
+    fn serializes_catalog() {
+        let options = LinguiOptions::default();
+
+        assert_eq!(
+            serialize(&sample_catalog(), Some("cs"), &options),
+            r#"msgid ""
+msgstr ""
+"MIME-Version: 1.0\n"
+"Content-Type: text/plain; charset=utf-8\n"
+"Content-Transfer-Encoding: 8bit\n"
+"X-Generator: lingui-swc\n"
+"Language: cs\n"
+
+#. translator note
+#: src/App.tsx:4
+msgid "Hello {name}"
+msgstr ""
+
+#. translator note
+#: src/App.tsx:4
+msgctxt "toolbar"
+msgid "Save"
+msgstr ""
+
+#. js-lingui-message: "Home"
+#: src/Nav.tsx:1
+#: src/Footer.tsx:9
+#, js-lingui-explicit-id
+msgid "nav.home"
+msgstr "Domů"
+
+#. translator note
+#: src/App.tsx:4
+msgid ""
+"Multiline\n"
+"message with \"quotes\""
+msgstr ""
+
+#, js-lingui-explicit-id
+#~ msgid "removed"
+#~ msgstr "Odstraněno"
+"#
+        );
+    }
+
+    #[test]
+    fn round_trips_catalog() {
+        let options = LinguiOptions::default();
+        let catalog = sample_catalog();
+
+        let parsed = parse(&serialize(&catalog, Some("cs"), &options), &options).unwrap();
+
+        assert_eq!(parsed, catalog);
+    }
+
+    #[test]
+    fn parses_origins_with_spaces() {
+        let options = LinguiOptions::default();
+        let catalog = parse(
+            "#: src/My Page.tsx:4 src/App.tsx:2\n#: src/Other Page.tsx:7\nmsgid \"Hello\"\nmsgstr \"\"\n",
+            &options,
+        )
+        .unwrap();
+
+        assert_eq!(
+            catalog.messages.values().next().unwrap().origins,
+            vec![
+                Origin("src/My Page.tsx".into(), 4),
+                Origin("src/App.tsx".into(), 2),
+                Origin("src/Other Page.tsx".into(), 7),
+            ]
+        );
+    }
+
+    #[test]
+    fn round_trips_comments_and_flags() {
+        let options = LinguiOptions::default();
+        let mut catalog = Catalog::default();
+        for comment in ["Shown on the home page", "  after the user signs in"] {
+            catalog.add(
+                "greeting".into(),
+                CatalogMessage {
+                    message: Some("Hello".into()),
+                    comments: vec![comment.into()],
+                    origins: vec![Origin("src/My Page.tsx".into(), 4)],
+                    flags: vec!["fuzzy".into()],
+                    ..Default::default()
+                },
+            );
+        }
+
+        let parsed = parse(&serialize(&catalog, None, &options), &options).unwrap();
+
+        assert_eq!(parsed.messages["greeting"].comments.len(), 2);
+        assert_eq!(parsed, catalog);
+    }
+
+    #[test]
+    fn reads_explicit_id_flag() {
+        let options = LinguiOptions::default();
+        let catalog = parse(
+            "#, fuzzy, js-lingui-explicit-id\nmsgid \"nav.home\"\nmsgstr \"Domů\"\n\n\
+             #. js-lingui-explicit-id\nmsgid \"nav.back\"\nmsgstr \"Zpět\"\n",
+            &options,
+        )
+        .unwrap();
+
+        assert_eq!(
+            catalog.messages.keys().collect::<Vec<_>>(),
+            vec!["nav.back", "nav.home"]
+        );
+        assert_eq!(catalog.messages["nav.home"].flags, vec!["fuzzy"]);
+        assert_eq!(catalog.messages["nav.home"].message, None);
+    }
+
+    #[test]
+    fn starts_entries_at_comments_without_blank_lines() {
+        let options = LinguiOptions::default();
+        let catalog = parse(
+            "#. first\n#: src/a.tsx:1\nmsgid \"One\"\nmsgstr \"Jedna\"\n\
+             #. second\n#: src/b.tsx:2\nmsgid \"Two\"\nmsgstr \"Dva\"\n",
+            &options,
+        )
+        .unwrap();
+
+        let mut messages: Vec<_> = catalog
+            .messages
+            .values()
+            .map(|message| {
+                (
+                    message.message.as_deref().unwrap(),
+                    message.comments.clone(),
+                    message.origins.clone(),
+                )
+            })
+            .collect();
+        messages.sort();
+
+        assert_eq!(
+            messages,
+            vec![
+                (
+                    "One",
+                    vec!["first".to_string()],
+                    vec![Origin("src/a.tsx".into(), 1)]
+                ),
+                (
+                    "Two",
+                    vec!["second".to_string()],
+                    vec![Origin("src/b.tsx".into(), 2)]
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn round_trips_lingui_v5_ids() {
+        let options = LinguiOptions {
+            use_lingui_v5_id_generation: true,
+            ..Default::default()
+        };
+        let mut catalog = Catalog::default();
+        catalog.add(
+            generate_message_id("Hello World", "my context", true),
+            CatalogMessage {
+                message: Some("Hello World".into()),
+                context: Some("my context".into()),
+                ..Default::default()
+            },
+        );
+
+        let po = serialize(&catalog, None, &options);
+        assert!(!po.contains(EXPLICIT_ID_FLAG));
+        assert_eq!(parse(&po, &options).unwrap(), catalog);
+    }
+
+    #[test]
+    fn round_trips_message_as_id() {
+        let options = LinguiOptions {
+            id_generation: IdGeneration {
+                strategy: IdStrategy::Message,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let mut catalog = Catalog::default();
+        catalog.add(
+            "Hello World".into(),
+            CatalogMessage {
+                message: Some("Hello World".into()),
+                ..Default::default()
+            },
+        );
+
+        let po = serialize(&catalog, None, &options);
+        assert!(!po.contains(EXPLICIT_ID_FLAG));
+        assert_eq!(parse(&po, &options).unwrap(), catalog);
+    }
+
+    #[test]
+    fn parses_lingui_id_comment() {
+        let catalog = parse(
+            "#. js-lingui-id: abc123\nmsgid \"Hello\"\nmsgstr \"Ahoj\"\n",
+            &Default::default(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            catalog.messages["abc123"].translation.as_deref(),
+            Some("Ahoj")
+        );
+    }
+
+    #[test]
+    fn rejects_gettext_plurals() {
+        let err = parse(
+            "msgid \"book\"\nmsgid_plural \"books\"\nmsgstr[0] \"\"\n",
+            &Default::default(),
+        )
+        .unwrap_err();
+
+        assert_eq!(err.line, 2);
+    }
+
+    #[test]
+    fn rejects_unterminated_strings() {
+        let err = parse("msgid \"Hello\nmsgstr \"\"\n", &Default::default()).unwrap_err();
+
+        assert_eq!(
+            err,
+            ParseError {
+                line: 1,
+                message: "expected a quoted string, found `\"Hello`".into()
+            }
+        );
+    }
+}
@@ -0,0 +1,750 @@
+//! XLIFF 2.0 serialization.
+//!
+//! Every message becomes a `<unit>` keyed by the message id. Value placeholders
+//! (`{name}`) are written as `<ph>` elements and JSX components (`<0>…</0>`) as
+//! `<pc>` pairs, so translation tools protect them instead of showing raw ICU
+//! syntax. The original placeholder text is stored in `<originalData>` and
+//! referenced with `dataRef`, which is what the importer uses to restore the
+//! message.
+//!
+//! Only the placeholder names collected during extraction are protected, other
+//! ICU syntax (plural or select arguments) is left as text. Obsolete messages
+//! are not exported.
+
+use super::{Catalog, CatalogMessage, Origin};
+use lingui_macro::icu::{read_tag, TagKind};
+use quick_xml::escape::{escape, partial_escape};
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use std::collections::{HashMap, VecDeque};
+use std::fmt::Write;
+
+const XLIFF_NS: &str = "urn:oasis:names:tc:xliff:document:2.0";
+
+#[derive(Debug, PartialEq)]
+pub struct ParseError {
+    pub position: u64,
+    pub message: String,
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "byte {}: {}", self.position, self.message)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+#[derive(Debug, PartialEq)]
+enum Inline {
+    Text(String),
+    /// standalone placeholder, `{name}` or a self-closing component `<0/>`
+    Ph(String),
+    /// paired component `<0>…</0>`
+    Pc {
+        start: String,
+        end: String,
+        children: Vec<Inline>,
+    },
+}
+
+/// Split a message into text and the placeholders known from extraction.
+fn tokenize(message: &str, placeholders: &[String], components: &[String]) -> Vec<Inline> {
+    // (opening tag, name, children collected so far)
+    let mut stack: Vec<(String, String, Vec<Inline>)> = Vec::new();
+    let mut current: Vec<Inline> = Vec::new();
+    let mut text = String::new();
+    let mut rest = message;
+
+    fn flush(text: &mut String, into: &mut Vec<Inline>) {
+        if !text.is_empty() {
+            into.push(Inline::Text(std::mem::take(text)));
+        }
+    }
+
+    while let Some(c) = rest.chars().next() {
+        if c == '{' {
+            if let Some(end) = rest.find('}') {
+                let name = &rest[1..end];
+                if placeholders.iter().any(|p| p == name) {
+                    flush(&mut text, &mut current);
+                    current.push(Inline::Ph(rest[..=end].into()));
+                    rest = &rest[end + 1..];
+                    continue;
+                }
+            }
+        }
+
+        if c == '<' {
+            if let Some((len, name, kind)) = read_tag(rest) {
+                if components.iter().any(|p| p == name) {
+                    let tag = &rest[..len];
+                    match kind {
+                        TagKind::SelfClosing => {
+                            flush(&mut text, &mut current);
+                            current.push(Inline::Ph(tag.into()));
+                        }
+                        TagKind::Opening => {
+                            flush(&mut text, &mut current);
+                            stack.push((tag.into(), name.into(), std::mem::take(&mut current)));
+                        }
+                        TagKind::Closing => {
+                            flush(&mut text, &mut current);
+                            match stack.last() {
+                                Some((_, open, _)) if open == name => {
+                                    let (start, _, parent) = stack.pop().unwrap();
+                                    let children = std::mem::replace(&mut current, parent);
+                                    current.push(Inline::Pc {
+                                        start,
+                                        end: tag.into(),
+                                        children,
+                                    });
+                                }
+                                // unbalanced, keep it protected on its own
+                                _ => current.push(Inline::Ph(tag.into())),
+                            }
+                        }
+                    }
+                    rest = &rest[len..];
+                    continue;
+                }
+            }
+        }
+
+        text.push(c);
+        rest = &rest[c.len_utf8()..];
+    }
+
+    flush(&mut text, &mut current);
+
+    // unclosed tags become standalone placeholders followed by their content
+    while let Some((start, _, parent)) = stack.pop() {
+        let children = std::mem::replace(&mut current, parent);
+        current.push(Inline::Ph(start));
+        current.extend(children);
+    }
+
+    current
+}
+
+/// Placeholder text to `<data>` ids and inline element ids of a single unit.
+#[derive(Default)]
+struct UnitData {
+    data: Vec<(String, String)>,
+    data_ids: HashMap<String, String>,
+    next_id: usize,
+    /// inline ids used in the source, reused in the target for the same data
+    source_ids: HashMap<String, VecDeque<String>>,
+}
+
+impl UnitData {
+    fn data_ref(&mut self, original: &str) -> String {
+        if let Some(id) = self.data_ids.get(original) {
+            return id.clone();
+        }
+        let id = format!("d{}", self.data_ids.len() + 1);
+        self.data_ids.insert(original.into(), id.clone());
+        self.data.push((id.clone(), original.into()));
+        id
+    }
+
+    fn inline_id(&mut self, key: &str, in_target: bool) -> String {
+        if in_target {
+            if let Some(id) = self.source_ids.get_mut(key).and_then(|ids| ids.pop_front()) {
+                return id;
+            }
+        }
+
+        self.next_id += 1;
+        let id = self.next_id.to_string();
+        if !in_target {
+            self.source_ids
+                .entry(key.into())
+                .or_default()
+                .push_back(id.clone());
+        }
+        id
+    }
+}
+
+fn write_inlines(out: &mut String, inlines: &[Inline], unit: &mut UnitData, in_target: bool) {
+    for inline in inlines {
+        match inline {
+            Inline::Text(text) => out.push_str(&partial_escape(text)),
+            Inline::Ph(original) => {
+                let data_ref = unit.data_ref(original);
+                let id = unit.inline_id(&data_ref, in_target);
+                let _ = write!(
+                    out,
+                    r#"<ph id="{id}" dataRef="{data_ref}" disp="{}"/>"#,
+                    escape(original)
+                );
+            }
+            Inline::Pc {
+                start,
+                end,
+                children,
+            } => {
+                let start_ref = unit.data_ref(start);
+                let end_ref = unit.data_ref(end);
+                let id = unit.inline_id(&format!("{start_ref} {end_ref}"), in_target);
+                let _ = write!(
+                    out,
+                    r#"<pc id="{id}" dataRefStart="{start_ref}" dataRefEnd="{end_ref}" dispStart="{}" dispEnd="{}">"#,
+                    escape(start),
+                    escape(end)
+                );
+                write_inlines(out, children, unit, in_target);
+                out.push_str("</pc>");
+            }
+        }
+    }
+}
+
+fn write_unit(out: &mut String, id: &str, message: &CatalogMessage) {
+    let _ = writeln!(out, r#"    <unit id="{}">"#, escape(id));
+
+    let mut notes = Vec::new();
+    if let Some(context) = &message.context {
+        notes.push(("context", context.clone()));
+    }
+    for comment in &message.comments {
+        notes.push(("comment", comment.clone()));
+    }
+    for origin in &message.origins {
+        notes.push(("location", origin.to_string()));
+    }
+
+    if !notes.is_empty() {
+        out.push_str("      <notes>\n");
+        for (category, note) in notes {
+            let _ = writeln!(
+                out,
+                r#"        <note category="{category}">{}</note>"#,
+                partial_escape(&note)
+            );
+        }
+        out.push_str("      </notes>\n");
+    }
+
+    let mut unit = UnitData::default();
+    let mut segment = String::new();
+
+    let source = tokenize(
+        message.message.as_deref().unwrap_or_default(),
+        &message.placeholders,
+        &message.components,
+    );
+    segment.push_str("        <source>");
+    write_inlines(&mut segment, &source, &mut unit, false);
+    segment.push_str("</source>\n");
+
+    if let Some(translation) = &message.translation {
+        let target = tokenize(translation, &message.placeholders, &message.components);
+        segment.push_str("        <target>");
+        write_inlines(&mut segment, &target, &mut unit, true);
+        segment.push_str("</target>\n");
+    }
+
+    if !unit.data.is_empty() {
+        out.push_str("      <originalData>\n");
+        for (id, original) in &unit.data {
+            let _ = writeln!(
+                out,
+                r#"        <data id="{id}">{}</data>"#,
+                partial_escape(original)
+            );
+        }
+        out.push_str("      </originalData>\n");
+    }
+
+    let state = if message.translation.is_some() {
+        "translated"
+    } else {
+        "initial"
+    };
+    let _ = writeln!(out, r#"      <segment state="{state}">"#);
+    out.push_str(&segment);
+    out.push_str("      </segment>\n");
+    out.push_str("    </unit>\n");
+}
+
+pub fn serialize(catalog: &Catalog, source_locale: &str, locale: Option<&str>) -> String {
+    let mut out = String::new();
+
+    out.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    let _ = write!(
+        out,
+        r#"<xliff xmlns="{XLIFF_NS}" version="2.0" srcLang="{}""#,
+        escape(source_locale)
+    );
+    if let Some(locale) = locale {
+        let _ = write!(out, r#" trgLang="{}""#, escape(locale));
+    }
+    out.push_str(">\n");
+    out.push_str("  <file id=\"messages\">\n");
+
+    for (id, message) in &catalog.messages {
+        if !message.obsolete {
+            write_unit(&mut out, id, message);
+        }
+    }
+
+    out.push_str("  </file>\n");
+    out.push_str("</xliff>\n");
+
+    out
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Text {
+    Note,
+    Data,
+    Source,
+    Target,
+}
+
+/// Inline content of a `<source>` or `<target>`, data references are
+/// resolved once the whole unit has been read because `<originalData>`
+/// may come after the segment.
+#[derive(Default)]
+struct Content {
+    parts: Vec<Part>,
+    /// `dataRefEnd` of the open `<pc>` elements
+    open_pcs: Vec<Option<String>>,
+}
+
+enum Part {
+    Text(String),
+    Ref(String),
+}
+
+impl Content {
+    fn push_ref(&mut self, e: &BytesStart, data_ref: &str, disp: &str) -> Result<(), String> {
+        let value = attribute(e, data_ref)?
+            .map(Part::Ref)
+            .or_else(|| attribute(e, disp).ok().flatten().map(Part::Text))
+            .ok_or_else(|| format!("inline element is missing `{data_ref}`"))?;
+        self.parts.push(value);
+        Ok(())
+    }
+
+    fn resolve(self, data: &HashMap<String, String>) -> Result<String, String> {
+        let mut out = String::new();
+        for part in self.parts {
+            match part {
+                Part::Text(text) => out.push_str(&text),
+                Part::Ref(id) => out.push_str(
+                    data.get(&id)
+                        .ok_or_else(|| format!("unknown data reference `{id}`"))?,
+                ),
+            }
+        }
+        Ok(out)
+    }
+}
+
+#[derive(Default)]
+struct Unit {
+    id: String,
+    context: Option<String>,
+    comments: Vec<String>,
+    origins: Vec<Origin>,
+    data: HashMap<String, String>,
+    source: Content,
+    target: Option<Content>,
+}
+
+impl Unit {
+    fn finish(self, catalog: &mut Catalog) -> Result<(), String> {
+        let mut placeholders = Vec::new();
+        let mut components = Vec::new();
+
+        let mut refs: Vec<&String> = self
+            .source
+            .parts
+            .iter()
+            .filter_map(|part| match part {
+                Part::Ref(id) => Some(id),
+                Part::Text(_) => None,
+            })
+            .collect();
+        refs.dedup();
+
+        for id in refs {
+            let Some(original) = self.data.get(id) else {
+                continue;
+            };
+            if let Some(name) = original.strip_prefix('{').and_then(|v| v.strip_suffix('}')) {
+                if !placeholders.iter().any(|p| p == name) {
+                    placeholders.push(name.to_string());
+                }
+            } else if let Some((_, name, TagKind::Opening | TagKind::SelfClosing)) =
+                read_tag(original)
+            {
+                if !components.iter().any(|c| c == name) {
+                    components.push(name.to_string());
+                }
+            }
+        }
+
+        let message = self.source.resolve(&self.data)?;
+        let translation = match self.target {
+            Some(target) => Some(target.resolve(&self.data)?),
+            None => None,
+        };
+
+        catalog.messages.insert(
+            self.id,
+            CatalogMessage {
+                message: Some(message).filter(|m| !m.is_empty()),
+                context: self.context,
+                comments: self.comments,
+                origins: self.origins,
+                flags: Vec::new(),
+                translation: translation.filter(|t| !t.is_empty()),
+                obsolete: false,
+                placeholders,
+                components,
+            },
+        );
+
+        Ok(())
+    }
+}
+
+fn attribute(e: &BytesStart, name: &str) -> Result<Option<String>, String> {
+    e.try_get_attribute(name)
+        .map_err(|err| err.to_string())?
+        .map(|attr| {
+            attr.unescape_value()
+                .map(|value| value.into_owned())
+                .map_err(|err| err.to_string())
+        })
+        .transpose()
+}
+
+pub fn parse(source: &str) -> Result<Catalog, ParseError> {
+    let mut reader = Reader::from_str(source);
+
+    let mut catalog = Catalog::default();
+    let mut unit: Option<Unit> = None;
+    let mut text: Option<(Text, String)> = None;
+    // (category, id) of the note or data element being read
+    let mut attr: Option<String> = None;
+
+    loop {
+        let position = reader.buffer_position();
+        let error = |message: String| ParseError { position, message };
+
+        let event = reader.read_event().map_err(|err| error(err.to_string()))?;
+
+        match event {
+            Event::Start(e) | Event::Empty(e) if e.local_name().as_ref() == b"unit" => {
+                let id = attribute(&e, "id")
+                    .map_err(error)?
+                    .ok_or_else(|| error("`unit` is missing `id`".into()))?;
+                unit = Some(Unit {
+                    id,
+                    ..Default::default()
+                });
+            }
+            Event::End(e) if e.local_name().as_ref() == b"unit" => {
+                if let Some(unit) = unit.take() {
+                    unit.finish(&mut catalog).map_err(error)?;
+                }
+            }
+            Event::Start(e) => {
+                let Some(unit) = unit.as_mut() else {
+                    continue;
+                };
+
+                match e.local_name().as_ref() {
+                    b"note" => {
+                        attr = attribute(&e, "category").map_err(error)?;
+                        text = Some((Text::Note, String::new()));
+                    }
+                    b"data" => {
+                        attr = attribute(&e, "id").map_err(error)?;
+                        text = Some((Text::Data, String::new()));
+                    }
+                    b"source" => {
+                        unit.source = Content::default();
+                        text = Some((Text::Source, String::new()));
+                    }
+                    b"target" => {
+                        unit.target = Some(Content::default());
+                        text = Some((Text::Target, String::new()));
+                    }
+                    b"pc" => {
+                        let content = match text {
+                            Some((Text::Source, _)) => &mut unit.source,
+                            Some((Text::Target, _)) => unit.target.get_or_insert_default(),
+                            _ => continue,
+                        };
+                        content
+                            .push_ref(&e, "dataRefStart", "dispStart")
+                            .map_err(error)?;
+                        let end = attribute(&e, "dataRefEnd").map_err(error)?;
+                        content.open_pcs.push(end);
+                    }
+                    _ => {}
+                }
+            }
+            Event::Empty(e) if e.local_name().as_ref() == b"ph" => {
+                let Some(unit) = unit.as_mut() else {
+                    continue;
+                };
+                let content = match text {
+                    Some((Text::Source, _)) => &mut unit.source,
+                    Some((Text::Target, _)) => unit.target.get_or_insert_default(),
+                    _ => continue,
+                };
+                content.push_ref(&e, "dataRef", "disp").map_err(error)?;
+            }
+            Event::Text(e) => {
+                let value = e.unescape().map_err(|err| error(err.to_string()))?;
+                match (&mut text, unit.as_mut()) {
+                    (Some((Text::Source, _)), Some(unit)) => {
+                        unit.source.parts.push(Part::Text(value.into_owned()))
+                    }
+                    (Some((Text::Target, _)), Some(unit)) => unit
+                        .target
+                        .get_or_insert_default()
+                        .parts
+                        .push(Part::Text(value.into_owned())),
+                    (Some((_, buffer)), _) => buffer.push_str(&value),
+                    _ => {}
+                }
+            }
+            Event::CData(e) => {
+                if let Some((_, buffer)) = &mut text {
+                    buffer.push_str(&String::from_utf8_lossy(&e));
+                }
+            }
+            Event::End(e) => {
+                let Some(unit) = unit.as_mut() else {
+                    continue;
+                };
+
+                match e.local_name().as_ref() {
+                    b"note" => {
+                        let (_, note) = text.take().unwrap_or((Text::Note, String::new()));
+                        match attr.take().as_deref() {
+                            Some("context") => unit.context = Some(note),
+                            Some("location") => unit.origins.push(Origin::parse(&note)),
+                            _ => unit.comments.push(note),
+                        }
+                    }
+                    b"data" => {
+                        let (_, value) = text.take().unwrap_or((Text::Data, String::new()));
+                        if let Some(id) = attr.take() {
+                            unit.data.insert(id, value);
+                        }
+                    }
+                    b"source" | b"target" => text = None,
+                    b"pc" => {
+                        let content = match text {
+                            Some((Text::Source, _)) => &mut unit.source,
+                            Some((Text::Target, _)) => unit.target.get_or_insert_default(),
+                            _ => continue,
+                        };
+                        if let Some(end) = content.open_pcs.pop().flatten() {
+                            content.parts.push(Part::Ref(end));
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    Ok(catalog)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_catalog() -> Catalog {
+        let mut catalog = Catalog::default();
+
+        catalog.add(
+            "greeting".into(),
+            CatalogMessage {
+                message: Some("Hello <0>{name}</0>, you have {count} new <1/> & more".into()),
+                context: Some("inbox".into()),
+                comments: vec!["shown on the dashboard".into()],
+                origins: vec![Origin("src/App.tsx".into(), 4)],
+                translation: Some("Máte {count} nových <1/>, <0>{name}</0>".into()),
+                placeholders: vec!["name".into(), "count".into()],
+                components: vec!["0".into(), "1".into()],
+                ..Default::default()
+            },
+        );
+
+        catalog.add(
+            "plural".into(),
+            CatalogMessage {
+                message: Some("{count, plural, one {# book} other {# books}}".into()),
+                placeholders: vec!["count".into()],
+                ..Default::default()
+            },
+        );
+
+        catalog
+    }
+
+    #[test]
+    fn tokenizes_placeholders_and_components() {
+        assert_eq!(
+            tokenize(
+                "Hi <0>{name}</0> {other} <b>",
+                &["name".into()],
+                &["0".into()]
+            ),
+            vec![
+                Inline::Text("Hi ".into()),
+                Inline::Pc {
+                    start: "<0>".into(),
+                    end: "</0>".into(),
+                    children: vec![Inline::Ph("{name}".into())],
+                },
+                Inline::Text(" {other} <b>".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn keeps_unbalanced_tags_as_placeholders() {
+        assert_eq!(
+            tokenize("a</0>b<0>c", &[], &["0".into()]),
+            vec![
+                Inline::Text("a".into()),
+                Inline::Ph("</0>".into()),
+                Inline::Text("b".into()),
+                Inline::Ph("<0>".into()),
+                Inline::Text("c".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn round_trips_named_components() {
+        let mut catalog = Catalog::default();
+        catalog.add(
+            "help".into(),
+            CatalogMessage {
+                message: Some("Read <help-link>the {topic} help</help-link><icon.close/>".into()),
+                translation: Some(
+                    "Přečtěte si <help-link>nápovědu</help-link><icon.close/>".into(),
+                ),
+                placeholders: vec!["topic".into()],
+                components: vec!["help-link".into(), "icon.close".into()],
+                ..Default::default()
+            },
+        );
+
+        let xliff = serialize(&catalog, "en", Some("cs"));
+
+        assert!(xliff.contains(
+            r#"<source>Read <pc id="1" dataRefStart="d1" dataRefEnd="d2" dispStart="&lt;help-link&gt;" dispEnd="&lt;/help-link&gt;">the <ph id="2" dataRef="d3" disp="{topic}"/> help</pc><ph id="3" dataRef="d4" disp="&lt;icon.close/&gt;"/></source>"#
+        ));
+        assert_eq!(parse(&xliff).unwrap(), catalog);
+    }
+
+    #[test]
+    fn serializes_catalog() {
+        assert_eq!(
+            serialize(&sample_catalog(), "en", Some("cs")),
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<xliff xmlns="urn:oasis:names:tc:xliff:document:2.0" version="2.0" srcLang="en" trgLang="cs">
+  <file id="messages">
+    <unit id="greeting">
+      <notes>
+        <note category="context">inbox</note>
+        <note category="comment">shown on the dashboard</note>
+        <note category="location">src/App.tsx:4</note>
+      </notes>
+      <originalData>
+        <data id="d1">&lt;0&gt;</data>
+        <data id="d2">&lt;/0&gt;</data>
+        <data id="d3">{name}</data>
+        <data id="d4">{count}</data>
+        <data id="d5">&lt;1/&gt;</data>
+      </originalData>
+      <segment state="translated">
+        <source>Hello <pc id="1" dataRefStart="d1" dataRefEnd="d2" dispStart="&lt;0&gt;" dispEnd="&lt;/0&gt;"><ph id="2" dataRef="d3" disp="{name}"/></pc>, you have <ph id="3" dataRef="d4" disp="{count}"/> new <ph id="4" dataRef="d5" disp="&lt;1/&gt;"/> &amp; more</source>
+        <target>Máte <ph id="3" dataRef="d4" disp="{count}"/> nových <ph id="4" dataRef="d5" disp="&lt;1/&gt;"/>, <pc id="1" dataRefStart="d1" dataRefEnd="d2" dispStart="&lt;0&gt;" dispEnd="&lt;/0&gt;"><ph id="2" dataRef="d3" disp="{name}"/></pc></target>
+      </segment>
+    </unit>
+    <unit id="plural">
+      <segment state="initial">
+        <source>{count, plural, one {# book} other {# books}}</source>
+      </segment>
+    </unit>
+  </file>
+</xliff>
+"#
+        );
+    }
+
+    #[test]
+    fn round_trips_catalog() {
+        let mut catalog = sample_catalog();
+
+        let parsed = parse(&serialize(&catalog, "en", Some("cs"))).unwrap();
+
+        // plural arguments are not protected, so their names are not recovered
+        catalog
+            .messages
+            .get_mut("plural")
+            .unwrap()
+            .placeholders
+            .clear();
+        assert_eq!(parsed, catalog);
+    }
+
+    #[test]
+    fn skips_obsolete_messages() {
+        let mut catalog = sample_catalog();
+        catalog.messages.get_mut("plural").unwrap().obsolete = true;
+
+        let parsed = parse(&serialize(&catalog, "en", None)).unwrap();
+
+        assert!(!parsed.messages.contains_key("plural"));
+    }
+
+    #[test]
+    fn parses_translated_file_without_original_data() {
+        let catalog = parse(
+            r#"<xliff xmlns="urn:oasis:names:tc:xliff:document:2.0" version="2.0" srcLang="en" trgLang="de">
+  <file id="f1">
+    <unit id="abc">
+      <segment>
+        <source>Hello <ph id="1" disp="{name}"/></source>
+        <target>Hallo <ph id="1" disp="{name}"/></target>
+      </segment>
+    </unit>
+  </file>
+</xliff>"#,
+        )
+        .unwrap();
+
+        let message = &catalog.messages["abc"];
+        assert_eq!(message.message.as_deref(), Some("Hello {name}"));
+        assert_eq!(message.translation.as_deref(), Some("Hallo {name}"));
+    }
+
+    #[test]
+    fn rejects_unknown_data_references() {
+        let err = parse(
+            r#"<xliff version="2.0"><file id="f"><unit id="a"><segment><source><ph id="1" dataRef="d9"/></source></segment></unit></file></xliff>"#,
+        )
+        .unwrap_err();
+
+        assert_eq!(err.message, "unknown data reference `d9`");
+    }
+}
@@ -0,0 +1,218 @@
+use crate::catalog::Catalog;
+use lingui_macro::icu::{compile_message, CompiledMessage, ParseError};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Module syntax of the compiled catalog, same as `compileNamespace` in `lingui.config`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum Namespace {
+    /// `module.exports = { messages }`
+    Cjs,
+    /// `export const messages`
+    Es,
+    /// `export const messages` typed as `Messages`, written as `.ts`
+    Ts,
+    /// plain JSON object, e.g. for the plugin `inlineCatalog` option, written as
+    /// `.compiled.json` so it can't replace a JSON catalog
+    Json,
+}
+
+impl Namespace {
+    /// Path of the compiled module, next to the catalog it was compiled from
+    pub fn output_path(&self, catalog: &Path) -> PathBuf {
+        catalog.with_extension(match self {
+            Namespace::Ts => "ts",
+            Namespace::Json => "compiled.json",
+            Namespace::Cjs | Namespace::Es => "js",
+        })
+    }
+}
+
+/// Whether writing `out` would replace one of the catalogs being compiled
+pub fn overwrites_catalog(out: &Path, catalogs: &[PathBuf]) -> bool {
+    let resolve = |path: &Path| std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    let out = resolve(out);
+
+    catalogs.iter().any(|catalog| resolve(catalog) == out)
+}
+
+#[derive(Debug)]
+pub struct CompileError {
+    pub id: String,
+    pub error: ParseError,
+}
+
+impl std::fmt::Display for CompileError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "message `{}`: {}", self.id, self.error)
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct CompiledCatalog {
+    pub messages: BTreeMap<String, CompiledMessage>,
+    /// ids without a translation, compiled from the source message instead
+    pub missing: Vec<String>,
+}
+
+/// Compile every non-obsolete message of a catalog. Untranslated messages fall
+/// back to the source message and then to the id, like the Lingui CLI does.
+pub fn compile_catalog(catalog: &Catalog) -> Result<CompiledCatalog, Vec<CompileError>> {
+    let mut compiled = CompiledCatalog::default();
+    let mut errors = Vec::new();
+
+    for (id, message) in &catalog.messages {
+        if message.obsolete {
+            continue;
+        }
+
+        if message.translation.is_none() {
+            compiled.missing.push(id.clone());
+        }
+
+        let text = message
+            .translation
+            .as_deref()
+            .or(message.message.as_deref())
+            .unwrap_or(id);
+
+        match compile_message(text) {
+            Ok(result) => {
+                compiled.messages.insert(id.clone(), result);
+            }
+            Err(error) => errors.push(CompileError {
+                id: id.clone(),
+                error,
+            }),
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(compiled)
+    } else {
+        Err(errors)
+    }
+}
+
+/// Render compiled messages as a JS module. The messages are embedded as a
+/// JSON string because `JSON.parse` is faster to load than an object literal.
+/// With [`Namespace::Json`] only the JSON itself is returned.
+pub fn to_module(messages: &BTreeMap<String, CompiledMessage>, namespace: Namespace) -> String {
+    let json = serde_json::to_string(messages).expect("compiled messages are serializable");
+    let literal = serde_json::to_string(&json).expect("strings are serializable");
+
+    match namespace {
+        Namespace::Cjs => {
+            format!("/*eslint-disable*/module.exports={{messages:JSON.parse({literal})}};")
+        }
+        Namespace::Es => format!("/*eslint-disable*/export const messages=JSON.parse({literal});"),
+        Namespace::Json => json,
+        Namespace::Ts => format!(
+            "/*eslint-disable*/import type{{Messages}}from\"@lingui/core\";export const messages=JSON.parse({literal})as Messages;"
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::catalog::CatalogMessage;
+
+    fn catalog() -> Catalog {
+        let mut catalog = Catalog::default();
+        catalog.add(
+            "a".into(),
+            CatalogMessage {
+                message: Some("Hello {name}".into()),
+                translation: Some("Ahoj {name}".into()),
+                ..Default::default()
+            },
+        );
+        catalog.add(
+            "b".into(),
+            CatalogMessage {
+                message: Some("{count, plural, one {# book} other {# books}}".into()),
+                ..Default::default()
+            },
+        );
+        catalog.add("c".into(), CatalogMessage::default());
+        catalog.messages.insert(
+            "d".into(),
+            CatalogMessage {
+                translation: Some("Removed".into()),
+                obsolete: true,
+                ..Default::default()
+            },
+        );
+        catalog
+    }
+
+    #[test]
+    fn compiles_catalog_with_fallbacks() {
+        let compiled = compile_catalog(&catalog()).unwrap();
+
+        assert_eq!(compiled.missing, vec!["b", "c"]);
+        assert_eq!(
+            serde_json::to_string(&compiled.messages).unwrap(),
+            r##"{"a":["Ahoj ",["name"]],"b":[["count","plural",{"one":["#"," book"],"other":["#"," books"]}]],"c":"c"}"##
+        );
+    }
+
+    #[test]
+    fn reports_invalid_messages() {
+        let mut catalog = Catalog::default();
+        catalog.add(
+            "broken".into(),
+            CatalogMessage {
+                message: Some("{count, plural, one {#} other {#}".into()),
+                ..Default::default()
+            },
+        );
+
+        let errors = compile_catalog(&catalog).unwrap_err();
+
+        assert_eq!(
+            errors[0].to_string(),
+            "message `broken`: Unterminated argument, expected `}` (at offset 0)"
+        );
+    }
+
+    #[test]
+    fn renders_modules() {
+        let mut messages = BTreeMap::new();
+        messages.insert("a".to_string(), compile_message("Hi \"you\"").unwrap());
+
+        assert_eq!(
+            to_module(&messages, Namespace::Cjs),
+            r#"/*eslint-disable*/module.exports={messages:JSON.parse("{\"a\":\"Hi \\\"you\\\"\"}")};"#
+        );
+        assert_eq!(
+            to_module(&messages, Namespace::Es),
+            r#"/*eslint-disable*/export const messages=JSON.parse("{\"a\":\"Hi \\\"you\\\"\"}");"#
+        );
+        assert_eq!(
+            to_module(&messages, Namespace::Json),
+            r#"{"a":"Hi \"you\""}"#
+        );
+        assert_eq!(
+            Namespace::Ts.output_path(Path::new("locales/cs.po")),
+            PathBuf::from("locales/cs.ts")
+        );
+    }
+
+    #[test]
+    fn keeps_json_catalogs() {
+        let catalogs = [PathBuf::from("locales/cs.json")];
+        let out = Namespace::Json.output_path(&catalogs[0]);
+
+        assert_eq!(out, PathBuf::from("locales/cs.compiled.json"));
+        assert!(!overwrites_catalog(&out, &catalogs));
+        assert!(overwrites_catalog(
+            &out,
+            &[
+                catalogs[0].clone(),
+                PathBuf::from("locales/cs.compiled.json")
+            ]
+        ));
+    }
+}
@@ -0,0 +1,352 @@
+use crate::catalog::{CatalogMessage, Origin};
+use lingui_macro::{LinguiMacroFolder, LinguiOptions, MessageCollector};
+use std::io;
+use std::path::{Component, Path, PathBuf};
+use swc_core::common::comments::SingleThreadedComments;
+use swc_core::common::errors::{EmitterWriter, Handler, HANDLER};
+use swc_core::common::sync::Lrc;
+use swc_core::common::{FileName, Globals, Mark, SourceMap, SourceMapper, GLOBALS};
+use swc_core::ecma::parser::{EsSyntax, Parser, StringInput, Syntax, TsSyntax};
+use swc_core::ecma::transforms::base::resolver;
+use swc_core::ecma::visit::{FoldWith, VisitMutWith};
+
+const EXTENSIONS: [&str; 4] = ["js", "jsx", "ts", "tsx"];
+
+/// Recursively collect source files from the given files and directories.
+/// `node_modules` and hidden directories are skipped, results are sorted.
+pub fn collect_files(paths: &[PathBuf]) -> io::Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+
+    for path in paths {
+        if path.is_dir() {
+            walk_dir(path, &mut files)?;
+        } else if is_source_file(path) {
+            files.push(path.clone());
+        }
+    }
+
+    files.sort();
+    files.dedup();
+
+    Ok(files)
+}
+
+fn walk_dir(dir: &Path, files: &mut Vec<PathBuf>) -> io::Result<()> {
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        let name = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or_default();
+
+        if path.is_dir() {
+            if name == "node_modules" || name.starts_with('.') {
+                continue;
+            }
+            walk_dir(&path, files)?;
+        } else if is_source_file(&path) {
+            files.push(path);
+        }
+    }
+
+    Ok(())
+}
+
+fn is_source_file(path: &Path) -> bool {
+    let name = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or_default();
+
+    !name.ends_with(".d.ts")
+        && path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| EXTENSIONS.contains(&ext))
+}
+
+fn syntax_for(file_name: &str) -> Syntax {
+    if file_name.ends_with(".ts") {
+        Syntax::Typescript(Default::default())
+    } else if file_name.ends_with(".tsx") {
+        Syntax::Typescript(TsSyntax {
+            tsx: true,
+            ..Default::default()
+        })
+    } else {
+        Syntax::Es(EsSyntax {
+            jsx: true,
+            ..Default::default()
+        })
+    }
+}
+
+/// Error returned when a file could not be parsed or the macro reported errors.
+/// The diagnostics themselves are already printed to stderr.
+#[derive(Debug)]
+pub struct ExtractError {
+    pub file_name: String,
+}
+
+impl std::fmt::Display for ExtractError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "failed to extract messages from {}", self.file_name)
+    }
+}
+
+impl std::error::Error for ExtractError {}
+
+/// Run the macro over a single source text in extraction mode and return the
+/// produced messages as catalog entries keyed by id.
+///
+/// Sources that don't mention any of the configured macro or custom macro
+/// packages are skipped without parsing.
+pub fn extract_source(
+    source: String,
+    file_name: &str,
+    options: &LinguiOptions,
+) -> Result<Vec<(String, CatalogMessage)>, ExtractError> {
+    let macro_packages = &options.macro_packages;
+    if !macro_packages
+        .core
+        .iter()
+        .chain(macro_packages.jsx.iter())
+        .chain(options.custom_macros.iter().map(|custom| &custom.package))
+        .any(|package| source.contains(package.as_str()))
+    {
+        return Ok(Vec::new());
+    }
+
+    let cm: Lrc<SourceMap> = Default::default();
+    let comments = SingleThreadedComments::default();
+    let collector = MessageCollector::new();
+
+    let emitter = EmitterWriter::new(Box::new(io::stderr()), Some(cm.clone()), false, false);
+    let handler = Handler::with_emitter(true, false, Box::new(emitter));
+
+    GLOBALS.set(&Globals::new(), || {
+        HANDLER.set(&handler, || {
+            let fm = cm.new_source_file(FileName::Real(file_name.into()).into(), source);
+            let mut parser = Parser::new(
+                syntax_for(file_name),
+                StringInput::from(&*fm),
+                Some(&comments),
+            );
+
+            let parsed = parser.parse_program();
+            for e in parser.take_errors() {
+                e.into_diagnostic(&handler).emit();
+            }
+
+            let mut program = match parsed {
+                Ok(program) => program,
+                Err(e) => {
+                    e.into_diagnostic(&handler).emit();
+                    return;
+                }
+            };
+
+            let mut folder = LinguiMacroFolder::new(
+                options.clone(),
+                Some(comments.clone()),
+                cm.clone() as Lrc<dyn SourceMapper>,
+            )
+            .with_message_collector(collector.clone());
+
+            program.visit_mut_with(&mut resolver(Mark::new(), Mark::new(), true));
+            program.fold_with(&mut folder);
+        })
+    });
+
+    if handler.has_errors() {
+        return Err(ExtractError {
+            file_name: file_name.into(),
+        });
+    }
+
+    Ok(collector
+        .take()
+        .into_iter()
+        .map(|msg| {
+            let line = cm.lookup_char_pos(msg.origin.lo).line;
+
+            (
+                msg.id,
+                CatalogMessage {
+                    message: msg.message,
+                    context: msg.context,
+                    comments: msg.comment.into_iter().collect(),
+                    origins: vec![Origin(file_name.into(), line)],
+                    flags: Vec::new(),
+                    translation: None,
+                    obsolete: false,
+                    placeholders: msg.placeholders,
+                    components: msg.components,
+                },
+            )
+        })
+        .collect())
+}
+
+/// Read and extract a file from disk. Origins are reported relative to `root`.
+pub fn extract_file(
+    path: &Path,
+    root: &Path,
+    options: &LinguiOptions,
+) -> Result<Vec<(String, CatalogMessage)>, Box<dyn std::error::Error>> {
+    let source = std::fs::read_to_string(path)?;
+
+    Ok(extract_source(
+        source,
+        &origin_file_name(path, root),
+        options,
+    )?)
+}
+
+/// Path of a source file as written in origins: relative to `root` with `/`
+/// separators, the same however the path was passed on the command line
+fn origin_file_name(path: &Path, root: &Path) -> String {
+    let mut absolute = PathBuf::new();
+    for component in root.join(path).components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                absolute.pop();
+            }
+            component => absolute.push(component),
+        }
+    }
+
+    absolute
+        .strip_prefix(root)
+        .unwrap_or(&absolute)
+        .to_string_lossy()
+        .replace('\\', "/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lingui_macro::{CustomMacro, CustomMacroArg, CustomMacroKind};
+
+    #[test]
+    fn extracts_messages_with_origins() {
+        let messages = extract_source(
+            r#"import { t } from "@lingui/core/macro";
+import { Trans } from "@lingui/react/macro";
+
+t`Hello ${name}`;
+const el = <Trans comment="title">Welcome <b>back</b></Trans>;
+"#
+            .into(),
+            "src/App.tsx",
+            &Default::default(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            messages,
+            vec![
+                (
+                    "OVaF9k".into(),
+                    CatalogMessage {
+                        message: Some("Hello {name}".into()),
+                        origins: vec![Origin("src/App.tsx".into(), 4)],
+                        placeholders: vec!["name".into()],
+                        ..Default::default()
+                    }
+                ),
+                (
+                    "T8kza2".into(),
+                    CatalogMessage {
+                        message: Some("Welcome <0>back</0>".into()),
+                        comments: vec!["title".into()],
+                        origins: vec![Origin("src/App.tsx".into(), 5)],
+                        components: vec!["0".into()],
+                        ..Default::default()
+                    }
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn extracts_sources_importing_only_custom_macros() {
+        let options = LinguiOptions {
+            custom_macros: vec![CustomMacro {
+                name: "tc".into(),
+                package: "@acme/i18n".into(),
+                kind: CustomMacroKind::T,
+                args: vec![CustomMacroArg::Context, CustomMacroArg::Message],
+            }],
+            ..Default::default()
+        };
+
+        let messages = extract_source(
+            r#"import { tc } from "@acme/i18n";
+
+tc("menu", "Open");
+"#
+            .into(),
+            "src/Menu.ts",
+            &options,
+        )
+        .unwrap();
+
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0].1.message.as_deref(), Some("Open"));
+        assert_eq!(messages[0].1.context.as_deref(), Some("menu"));
+    }
+
+    #[test]
+    fn skips_sources_without_macro_imports() {
+        let messages = extract_source("t`Hello`".into(), "plain.js", &Default::default()).unwrap();
+
+        assert!(messages.is_empty());
+    }
+
+    #[test]
+    fn reports_parse_errors() {
+        let result = extract_source(
+            r#"import { t } from "@lingui/core/macro"; t`"#.into(),
+            "broken.js",
+            &Default::default(),
+        );
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn origins_are_relative_to_root() {
+        let root = Path::new("/project");
+
+        assert_eq!(
+            origin_file_name(Path::new("./src/App.tsx"), root),
+            "src/App.tsx"
+        );
+        assert_eq!(
+            origin_file_name(Path::new("src/App.tsx"), root),
+            "src/App.tsx"
+        );
+        assert_eq!(
+            origin_file_name(Path::new("src/../lib/./util.ts"), root),
+            "lib/util.ts"
+        );
+        assert_eq!(
+            origin_file_name(Path::new("/project/src/App.tsx"), root),
+            "src/App.tsx"
+        );
+        assert_eq!(
+            origin_file_name(Path::new("/elsewhere/App.tsx"), root),
+            "/elsewhere/App.tsx"
+        );
+    }
+
+    #[test]
+    fn detects_source_files() {
+        assert!(is_source_file(Path::new("src/App.tsx")));
+        assert!(is_source_file(Path::new("src/index.js")));
+        assert!(!is_source_file(Path::new("src/types.d.ts")));
+        assert!(!is_source_file(Path::new("README.md")));
+    }
+}
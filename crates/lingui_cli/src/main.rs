@@ -0,0 +1,200 @@
+use clap::{Args, Parser, Subcommand};
+use lingui_macro::{LinguiJsOptions, LinguiOptions};
+use std::error::Error;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+mod catalog;
+mod compile;
+mod extract;
+
+use catalog::{Catalog, CatalogFormat};
+
+/// Native Lingui tooling built on the same transform as the SWC plugin.
+#[derive(Parser)]
+#[command(name = "lingui-swc", version, about)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Extract messages from source files into a single catalog
+    Extract(ExtractArgs),
+    /// Compile catalogs into JS modules loaded by the Lingui runtime
+    Compile(CompileArgs),
+}
+
+#[derive(Args)]
+struct ExtractArgs {
+    /// Files or directories to scan for `.js`, `.jsx`, `.ts` and `.tsx` sources
+    #[arg(default_value = ".")]
+    paths: Vec<PathBuf>,
+
+    /// Write the catalog to this file instead of stdout
+    #[arg(short, long)]
+    out: Option<PathBuf>,
+
+    /// Catalog format, guessed from the `--out` extension when omitted
+    #[arg(short, long, value_enum)]
+    format: Option<CatalogFormat>,
+
+    /// Locale of the catalog, written into the PO header or as XLIFF `trgLang`
+    #[arg(short, long)]
+    locale: Option<String>,
+
+    /// Locale of the source messages, written as XLIFF `srcLang`
+    #[arg(long, default_value = "en")]
+    source_locale: String,
+
+    /// JSON file with plugin options, the same object passed to `@lingui/swc-plugin`
+    #[arg(short, long)]
+    config: Option<PathBuf>,
+}
+
+#[derive(Args)]
+struct CompileArgs {
+    /// Catalog files, one per locale. Each is compiled into a module next to it
+    #[arg(required = true)]
+    catalogs: Vec<PathBuf>,
+
+    /// Catalog format, guessed from each file extension when omitted
+    #[arg(short, long, value_enum)]
+    format: Option<CatalogFormat>,
+
+    /// Module syntax of the compiled catalogs
+    #[arg(short, long, value_enum, default_value = "cjs")]
+    namespace: compile::Namespace,
+
+    /// JSON file with plugin options, the same object passed to `@lingui/swc-plugin`
+    #[arg(short, long)]
+    config: Option<PathBuf>,
+}
+
+fn load_options(config: Option<&Path>) -> Result<LinguiOptions, Box<dyn Error>> {
+    let js_options = match config {
+        Some(path) => serde_json::from_str::<LinguiJsOptions>(&std::fs::read_to_string(path)?)?,
+        None => LinguiJsOptions::default(),
+    };
+
+    Ok(js_options.into_options("development"))
+}
+
+fn run_extract(args: ExtractArgs) -> Result<ExitCode, Box<dyn Error>> {
+    let options = load_options(args.config.as_deref())?;
+    let root = std::env::current_dir()?;
+    let files = extract::collect_files(&args.paths)?;
+
+    let mut catalog = Catalog::default();
+    let mut failed = false;
+
+    // keep going to report the errors of all files at once
+    for file in &files {
+        match extract::extract_file(file, &root, &options) {
+            Ok(messages) => {
+                for (id, message) in messages {
+                    catalog.add(id, message);
+                }
+            }
+            Err(err) => {
+                eprintln!("{err}");
+                failed = true;
+            }
+        }
+    }
+
+    // a partial catalog would mark the messages of the failed files as obsolete
+    if failed {
+        eprintln!("Catalog not written, fix the errors above and run the extraction again");
+        return Ok(ExitCode::FAILURE);
+    }
+
+    let format = args.format.unwrap_or_else(|| match &args.out {
+        Some(path) => CatalogFormat::from_path(path),
+        None => CatalogFormat::Json,
+    });
+
+    // keep translations from a catalog written by a previous run
+    if let Some(path) = args.out.as_deref().filter(|path| path.exists()) {
+        let previous = format
+            .parse(&std::fs::read_to_string(path)?, &options)
+            .map_err(|err| format!("{}: {err}", path.display()))?;
+        catalog.merge_previous(previous);
+    }
+
+    let output = format.serialize(
+        &catalog,
+        &args.source_locale,
+        args.locale.as_deref(),
+        &options,
+    );
+    match &args.out {
+        Some(path) => std::fs::write(path, output)?,
+        None => println!("{output}"),
+    }
+
+    eprintln!(
+        "Extracted {} messages from {} files",
+        catalog.messages.len(),
+        files.len()
+    );
+
+    Ok(ExitCode::SUCCESS)
+}
+
+fn run_compile(args: CompileArgs) -> Result<ExitCode, Box<dyn Error>> {
+    let options = load_options(args.config.as_deref())?;
+    let mut failed = false;
+
+    for path in &args.catalogs {
+        let format = args
+            .format
+            .unwrap_or_else(|| CatalogFormat::from_path(path));
+        let catalog = format
+            .parse(&std::fs::read_to_string(path)?, &options)
+            .map_err(|err| format!("{}: {err}", path.display()))?;
+
+        let compiled = match compile::compile_catalog(&catalog) {
+            Ok(compiled) => compiled,
+            Err(errors) => {
+                for err in errors {
+                    eprintln!("{}: {err}", path.display());
+                }
+                failed = true;
+                continue;
+            }
+        };
+
+        let out = args.namespace.output_path(path);
+        if compile::overwrites_catalog(&out, &args.catalogs) {
+            eprintln!(
+                "{}: not written, it is one of the catalogs being compiled",
+                out.display()
+            );
+            failed = true;
+            continue;
+        }
+        std::fs::write(&out, compile::to_module(&compiled.messages, args.namespace))?;
+
+        eprintln!(
+            "Compiled {} messages into {} ({} missing translations)",
+            compiled.messages.len(),
+            out.display(),
+            compiled.missing.len()
+        );
+    }
+
+    Ok(if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    })
+}
+
+fn main() -> Result<ExitCode, Box<dyn Error>> {
+    match Cli::parse().command {
+        Command::Extract(args) => run_extract(args),
+        Command::Compile(args) => run_compile(args),
+    }
+}
@@ -1,7 +1,7 @@
 use std::collections::HashSet;
 use swc_core::atoms::atom;
 use swc_core::common::comments::{Comment, CommentKind, Comments};
-use swc_core::common::{EqIgnoreSpan, Span, DUMMY_SP};
+use swc_core::common::{EqIgnoreSpan, Span, SyntaxContext, DUMMY_SP};
 use swc_core::ecma::ast::*;
 use swc_core::ecma::atoms::Atom;
 use swc_core::ecma::utils::quote_ident;
@@ -20,6 +20,12 @@ pub fn get_jsx_attr<'a>(el: &'a JSXOpeningElement, name: &str) -> Option<&'a JSX
     None
 }
 
+/// Whether `text` has letters a user would read, as opposed to whitespace,
+/// punctuation or numbers
+pub fn has_words(text: &str) -> bool {
+    text.chars().any(char::is_alphabetic)
+}
+
 // get_local_ident_from_object_pat_prop(prop, "t")
 // const {t} = useLingui() // => Ident("t")
 // const {t: _} = useLingui() // => Ident("_")
@@ -118,6 +124,26 @@ pub fn omit_jsx_attrs(
     attrs
 }
 
+/// Source name of a JSX tag: `a`, `Icons.Star` or `svg:path`
+pub fn jsx_element_name(name: &JSXElementName) -> String {
+    fn object_name(obj: &JSXObject) -> String {
+        match obj {
+            JSXObject::Ident(ident) => ident.sym.to_string(),
+            JSXObject::JSXMemberExpr(member) => {
+                format!("{}.{}", object_name(&member.obj), member.prop.sym)
+            }
+        }
+    }
+
+    match name {
+        JSXElementName::Ident(ident) => ident.sym.to_string(),
+        JSXElementName::JSXMemberExpr(member) => {
+            format!("{}.{}", object_name(&member.obj), member.prop.sym)
+        }
+        JSXElementName::JSXNamespacedName(name) => format!("{}:{}", name.ns.sym, name.name.sym),
+    }
+}
+
 pub fn is_jsx_elements_equal(a: &JSXOpeningElement, b: &JSXOpeningElement) -> bool {
     if !a.name.eq_ignore_span(&b.name) {
         return false;
@@ -192,6 +218,47 @@ pub fn create_key_value_prop(key: &str, value: Box<Expr>) -> PropOrSpread {
     })))
 }
 
+/// Build a JS literal from a JSON value, used to inline compiled messages.
+pub fn json_to_expr(value: &serde_json::Value) -> Box<Expr> {
+    use serde_json::Value;
+
+    Box::new(match value {
+        Value::Null => Expr::Lit(Lit::Null(Null { span: DUMMY_SP })),
+        Value::Bool(value) => (*value).into(),
+        Value::Number(number) => number.as_f64().unwrap_or_default().into(),
+        Value::String(value) => value.as_str().into(),
+        Value::Array(items) => Expr::Array(ArrayLit {
+            span: DUMMY_SP,
+            elems: items
+                .iter()
+                .map(|item| {
+                    Some(ExprOrSpread {
+                        spread: None,
+                        expr: json_to_expr(item),
+                    })
+                })
+                .collect(),
+        }),
+        Value::Object(map) => Expr::Object(ObjectLit {
+            span: DUMMY_SP,
+            props: map
+                .iter()
+                .map(|(key, value)| {
+                    let key = if Ident::verify_symbol(key).is_ok() {
+                        PropName::Ident(quote_ident!(key.as_str()))
+                    } else {
+                        PropName::Str(key.as_str().into())
+                    };
+                    PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
+                        key,
+                        value: json_to_expr(value),
+                    })))
+                })
+                .collect(),
+        }),
+    })
+}
+
 pub fn create_import(source: Atom, imported: IdentName, local: IdentName) -> ModuleItem {
     ModuleItem::ModuleDecl(ModuleDecl::Import(ImportDecl {
         span: DUMMY_SP,
@@ -212,6 +279,63 @@ pub fn create_import(source: Atom, imported: IdentName, local: IdentName) -> Mod
     }))
 }
 
+/// const { imported: local } = require("source");
+pub fn create_require(source: Atom, imported: IdentName, local: IdentName) -> Stmt {
+    Stmt::Decl(Decl::Var(Box::new(VarDecl {
+        span: DUMMY_SP,
+        ctxt: SyntaxContext::empty(),
+        kind: VarDeclKind::Const,
+        declare: false,
+        decls: vec![VarDeclarator {
+            span: DUMMY_SP,
+            name: Pat::Object(ObjectPat {
+                span: DUMMY_SP,
+                props: vec![ObjectPatProp::KeyValue(KeyValuePatProp {
+                    key: PropName::Ident(imported),
+                    value: Box::new(Pat::Ident(Ident::from(local).into())),
+                })],
+                optional: false,
+                type_ann: None,
+            }),
+            init: Some(Box::new(Expr::Call(CallExpr {
+                span: DUMMY_SP,
+                ctxt: SyntaxContext::empty(),
+                callee: Callee::Expr(Box::new(Expr::Ident(quote_ident!("require").into()))),
+                args: vec![ExprOrSpread {
+                    spread: None,
+                    expr: Box::new(Expr::Lit(Lit::Str(Str {
+                        span: DUMMY_SP,
+                        value: source.to_string().into(),
+                        raw: None,
+                    }))),
+                }],
+                type_args: None,
+            }))),
+            definite: false,
+        }],
+    })))
+}
+
+/// "source" of a `require("source")` call
+pub fn get_require_source(expr: Option<&Expr>) -> Option<String> {
+    let Expr::Call(call) = expr? else {
+        return None;
+    };
+    let Callee::Expr(callee) = &call.callee else {
+        return None;
+    };
+
+    match (&**callee, call.args.as_slice()) {
+        (Expr::Ident(ident), [arg]) if ident.sym == "require" && arg.spread.is_none() => {
+            match &*arg.expr {
+                Expr::Lit(Lit::Str(source)) => Some(source.value.to_string_lossy().into_owned()),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
 pub fn add_i18n_comment<C: Comments>(comments: &Option<C>, span: Span, use_jsdoc: bool) {
     if let Some(comments) = &comments {
         comments.add_leading(
@@ -1,40 +1,21 @@
 use crate::ast_utils::{
-    get_jsx_attr, get_jsx_attr_value_as_string, is_jsx_elements_equal, omit_jsx_attrs,
+    get_jsx_attr, get_jsx_attr_value_as_string, is_jsx_elements_equal, jsx_element_name,
+    omit_jsx_attrs,
 };
+use crate::diagnostics::{Code, Diagnostic};
+use crate::icu::is_valid_placeholder_name;
 use crate::options::LinguiOptions;
 use crate::tokens::{CaseOrOffset, MsgArg, MsgToken};
 use std::collections::HashSet;
-use swc_core::{common::DUMMY_SP, ecma::ast::*};
+use swc_core::{
+    common::{Span, DUMMY_SP},
+    ecma::ast::*,
+};
 
 fn is_numeric(s: &str) -> bool {
     !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit())
 }
 
-fn is_valid_placeholder_name(s: &str) -> bool {
-    if s.is_empty() {
-        return false;
-    }
-
-    let bytes = s.as_bytes();
-    let first = bytes[0];
-    if !(first.is_ascii_alphabetic() || first == b'_') {
-        return false;
-    }
-
-    if bytes.len() == 1 {
-        return true;
-    }
-
-    let last = bytes[bytes.len() - 1];
-    if !(last.is_ascii_alphanumeric() || last == b'_') {
-        return false;
-    }
-
-    bytes[1..bytes.len() - 1]
-        .iter()
-        .all(|&b| b.is_ascii_alphanumeric() || b == b'_' || b == b'.' || b == b'-')
-}
-
 fn dedup_values(mut v: Vec<ValueWithPlaceholder>) -> Vec<ValueWithPlaceholder> {
     let mut uniques = HashSet::new();
     v.retain(|e| uniques.insert(e.placeholder.clone()));
@@ -74,6 +55,10 @@ pub struct MessageBuilderResult {
     pub message: Box<Expr>,
     pub values: Option<Box<Expr>>,
     pub components: Option<Box<Expr>>,
+    /// names of the value placeholders, in order of appearance
+    pub placeholders: Vec<String>,
+    /// names of the component placeholders, in order of appearance
+    pub component_names: Vec<String>,
 }
 
 pub struct MessageBuilder<'a> {
@@ -114,15 +99,20 @@ impl<'a> MessageBuilder<'a> {
             raw: None,
         })));
 
-        let values = if self.values.is_empty() {
+        let values = dedup_values(self.values);
+        let placeholders = values.iter().map(|v| v.placeholder.clone()).collect();
+        let component_names = self
+            .components
+            .iter()
+            .map(|c| c.placeholder.clone())
+            .collect();
+
+        let values = if values.is_empty() {
             None
         } else {
             Some(Box::new(Expr::Object(ObjectLit {
                 span: DUMMY_SP,
-                props: dedup_values(self.values)
-                    .into_iter()
-                    .map(|item| item.into_prop())
-                    .collect(),
+                props: values.into_iter().map(|item| item.into_prop()).collect(),
             })))
         };
 
@@ -144,6 +134,8 @@ impl<'a> MessageBuilder<'a> {
             message,
             values,
             components,
+            placeholders,
+            component_names,
         }
     }
 
@@ -161,8 +153,8 @@ impl<'a> MessageBuilder<'a> {
                 MsgToken::TagOpening(val) => {
                     self.push_tag_opening(val.el, val.self_closing);
                 }
-                MsgToken::TagClosing => {
-                    self.push_tag_closing();
+                MsgToken::TagClosing(span) => {
+                    self.push_tag_closing(span);
                 }
             }
         }
@@ -182,13 +174,12 @@ impl<'a> MessageBuilder<'a> {
                 attr.and_then(|attr| get_jsx_attr_value_as_string(attr.value.as_ref()?));
 
             if attr.is_some() && attr_value.is_none() {
-                swc_core::plugin::errors::HANDLER.with(|h| {
-                    h.struct_span_err(
-                        el.span,
-                        &format!("The `{attr_name}` attribute must be a non-empty string literal."),
-                    )
-                    .emit();
-                });
+                Diagnostic::new(
+                    Code::InvalidPlaceholderAttribute,
+                    el.span,
+                    format!("The `{attr_name}` attribute must be a non-empty string literal."),
+                )
+                .emit();
             }
 
             base_name = attr_value;
@@ -198,46 +189,46 @@ impl<'a> MessageBuilder<'a> {
 
         if base_name.is_none() {
             if let Some(defaults) = &self.options.jsx_placeholder_defaults {
-                if let JSXElementName::Ident(ident) = &el.name {
-                    if let Some(def) = defaults.get(&ident.sym.to_string()) {
-                        base_name = Some(def.into());
-                    }
+                if let Some(def) = defaults.get(&jsx_element_name(&el.name)) {
+                    base_name = Some(def.into());
                 }
             }
         }
 
         let name = if let Some(n) = base_name {
             if is_numeric(&n) {
-                swc_core::plugin::errors::HANDLER.with(|h| {
-                    h.struct_span_err(
-                        el.span,
-                        &format!("Placeholder name `{n}` is not allowed because it conflicts with auto-generated numeric placeholders. Use a non-numeric name instead."),
-                    ).emit();
-                });
+                Diagnostic::new(
+                    Code::NumericPlaceholderName,
+                    el.span,
+                    format!("Placeholder name `{n}` is not allowed because it conflicts with auto-generated numeric placeholders. Use a non-numeric name instead."),
+                )
+                .emit();
             } else if !is_valid_placeholder_name(&n) {
-                swc_core::plugin::errors::HANDLER.with(|h| {
-                    h.struct_span_err(
-                        el.span,
-                        &format!("Placeholder name `{n}` is not valid. Names must start and end with a letter/digit/underscore, but may contain `.-` in between."),
-                    ).emit();
-                });
+                Diagnostic::new(
+                    Code::InvalidPlaceholderName,
+                    el.span,
+                    format!("Placeholder name `{n}` is not valid. Names must start and end with a letter/digit/underscore, but may contain `.-` in between."),
+                )
+                .emit();
             }
 
             if let Some((_, orig_el)) = self.elements_tracking.iter().find(|(k, _)| k == &n) {
                 if !is_jsx_elements_equal(&el, orig_el) {
-                    swc_core::plugin::errors::HANDLER.with(|h| {
-                        let attr_name = self.options.jsx_placeholder_attribute.as_deref().unwrap_or("_t");
-                        let eg = format!("(e.g. `<element {attr_name}=\"newName\" />`)");
-                        let msg = format!(
-                            "Multiple distinct JSX elements with the same placeholder name (`{n}`). Differentiate them by {} {eg}.",
-                            if self.options.jsx_placeholder_attribute.is_some() {
-                                format!("adding/modifying the `{attr_name}` attribute")
-                            } else {
-                                "setting `macro.jsxPlaceholderAttribute` in the lingui config and then adding the attribute to your JSX elements".to_string()
-                            }
-                        );
-                        h.struct_span_err(el.span, &msg).emit();
-                    });
+                    let attr_name = self
+                        .options
+                        .jsx_placeholder_attribute
+                        .as_deref()
+                        .unwrap_or("_t");
+                    let eg = format!("(e.g. `<element {attr_name}=\"newName\" />`)");
+                    let msg = format!(
+                        "Multiple distinct JSX elements with the same placeholder name (`{n}`). Differentiate them by {} {eg}.",
+                        if self.options.jsx_placeholder_attribute.is_some() {
+                            format!("adding/modifying the `{attr_name}` attribute")
+                        } else {
+                            "setting `macro.jsxPlaceholderAttribute` in the lingui config and then adding the attribute to your JSX elements".to_string()
+                        }
+                    );
+                    Diagnostic::new(Code::DuplicatePlaceholder, el.span, msg).emit();
                 }
             } else {
                 self.elements_tracking.push((n.clone(), el.clone()));
@@ -272,11 +263,17 @@ impl<'a> MessageBuilder<'a> {
         }
     }
 
-    fn push_tag_closing(&mut self) {
+    fn push_tag_closing(&mut self, span: Span) {
         if let Some(name) = self.components_stack.pop() {
             self.push_msg(&format!("</{name}>"));
         } else {
-            // todo JSX tags mismatch. write tests for tags mismatch, swc should not crash in that case
+            Diagnostic::new(
+                Code::MismatchedTag,
+                span,
+                "Closing tag without an opening tag in the message.",
+            )
+            .help("`<Plural>`, `<Select>` and `<SelectOrdinal>` take their cases as props and can't have children. Make the element self-closing and move its content into a case, e.g. `other`.")
+            .emit();
         }
     }
 
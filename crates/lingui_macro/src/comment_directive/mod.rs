@@ -1,5 +1,5 @@
+use crate::diagnostics::{Code, Diagnostic};
 use swc_core::common::{BytePos, Span};
-use swc_core::plugin::errors::HANDLER;
 
 #[derive(Debug, Clone, PartialEq, Eq, Default)]
 pub struct DirectiveValues {
@@ -314,7 +314,7 @@ fn collect_lingui_directives_from_source(source: &str, start_pos: BytePos) -> Ve
                     comment_start,
                     BytePos(start_pos.0 + located.comment_end as u32),
                 );
-                HANDLER.with(|handler| handler.struct_span_err(span, &message).emit());
+                Diagnostic::new(Code::InvalidDirective, span, message).emit();
             }
         }
     }
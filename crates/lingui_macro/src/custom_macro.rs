@@ -0,0 +1,141 @@
+//! Expansion of the `customMacros` option.
+//!
+//! Calls of user defined macros are rewritten into the built-in macro they
+//! stand for before the regular transform runs, e.g. with
+//! `tc: ["context", "message"]` and `tn: ["count", "one", "other"]`:
+//!
+//! ```js
+//! tc("verb", `Open ${file}`)   // t({ context: "verb", message: `Open ${file}` })
+//! tn(count, "# file", "# files") // t({ message: plural(count, { one: "# file", other: "# files" }) })
+//! ```
+
+use crate::ast_utils::create_key_value_prop;
+use crate::diagnostics::{Code, Diagnostic};
+use crate::macro_utils::TransformCtx;
+use crate::options::{CustomMacro, CustomMacroArg, CustomMacroKind};
+use swc_core::common::{Spanned, SyntaxContext};
+use swc_core::ecma::ast::*;
+use swc_core::ecma::visit::{Fold, FoldWith};
+
+pub struct CustomMacroExpander<'a> {
+    pub ctx: &'a mut TransformCtx,
+}
+
+impl CustomMacroExpander<'_> {
+    fn expand(&mut self, custom: &CustomMacro, call: &CallExpr) -> Option<Expr> {
+        if call.args.len() != custom.args.len() || call.args.iter().any(|arg| arg.spread.is_some())
+        {
+            let roles = custom
+                .args
+                .iter()
+                .filter_map(|arg| serde_json::to_value(arg).ok())
+                .filter_map(|arg| arg.as_str().map(str::to_string))
+                .collect::<Vec<_>>()
+                .join(", ");
+            Diagnostic::new(
+                Code::CustomMacroArguments,
+                call.span,
+                format!(
+                    "`{}` expects {} arguments: {roles}.",
+                    custom.name,
+                    custom.args.len()
+                ),
+            )
+            .emit();
+            return None;
+        }
+
+        let mut props: Vec<PropOrSpread> = vec![];
+        let mut message = None;
+        let mut count = None;
+        let mut cases: Vec<PropOrSpread> = vec![];
+
+        for (role, arg) in custom.args.iter().zip(&call.args) {
+            let value = arg.expr.clone();
+            match role {
+                CustomMacroArg::Message => message = Some(value),
+                CustomMacroArg::Context => props.push(create_key_value_prop("context", value)),
+                CustomMacroArg::Comment => props.push(create_key_value_prop("comment", value)),
+                CustomMacroArg::Id => props.push(create_key_value_prop("id", value)),
+                CustomMacroArg::Count => count = Some(value),
+                case => {
+                    let key = CustomMacro::plural_case(*case).expect("remaining roles are cases");
+                    cases.push(create_key_value_prop(key, value));
+                }
+            }
+        }
+
+        let message = match (message, count) {
+            (Some(message), None) if cases.is_empty() => message,
+            (None, Some(count)) if !cases.is_empty() => Box::new(Expr::Call(CallExpr {
+                span: call.span,
+                ctxt: SyntaxContext::empty(),
+                callee: Callee::Expr(Box::new(
+                    self.ctx.builtin_macro_ident("plural", call.span).into(),
+                )),
+                args: vec![
+                    count.into(),
+                    Box::new(Expr::Object(ObjectLit {
+                        span: call.span,
+                        props: cases,
+                    }))
+                    .into(),
+                ],
+                type_args: None,
+            })),
+            _ => {
+                Diagnostic::new(
+                    Code::InvalidCustomMacro,
+                    call.span,
+                    format!(
+                        "Custom macro `{}` must have either a `message` argument, or a `count` argument with plural cases.",
+                        custom.name
+                    ),
+                )
+                .emit();
+                return None;
+            }
+        };
+        props.push(create_key_value_prop("message", message));
+
+        let export = match custom.kind {
+            CustomMacroKind::T => "t",
+            CustomMacroKind::Msg => "msg",
+        };
+
+        Some(Expr::Call(CallExpr {
+            span: call.span,
+            ctxt: SyntaxContext::empty(),
+            callee: Callee::Expr(Box::new(
+                self.ctx.builtin_macro_ident(export, call.span).into(),
+            )),
+            args: vec![Box::new(Expr::Object(ObjectLit {
+                // the i18n comment is attached to the descriptor
+                span: call.args[0].span(),
+                props,
+            }))
+            .into()],
+            type_args: None,
+        }))
+    }
+}
+
+impl Fold for CustomMacroExpander<'_> {
+    fn fold_expr(&mut self, expr: Expr) -> Expr {
+        let expr = expr.fold_children_with(self);
+
+        if let Expr::Call(call) = &expr {
+            if let Callee::Expr(callee) = &call.callee {
+                if let Expr::Ident(ident) = &**callee {
+                    if let Some(custom) = self.ctx.get_custom_macro(ident).cloned() {
+                        if let Some(expanded) = self.expand(&custom, call) {
+                            return expanded;
+                        }
+                    }
+                }
+            }
+        }
+
+        expr
+    }
+}
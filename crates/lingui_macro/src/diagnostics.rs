@@ -0,0 +1,379 @@
+//! Diagnostics reported by the macros.
+//!
+//! Every diagnostic has a stable code and name, e.g. `LINGUI001`
+//! `duplicate-placeholder`, so tools can group and suppress them. They are
+//! emitted through the SWC handler, either as text with the code in the header
+//! (`error[LINGUI001]: …`) or, with `diagnosticsFormat: "json"`, as a single
+//! line of JSON per diagnostic.
+//!
+//! The output settings are installed with [`with_config`] for the duration of
+//! a transform, so diagnostics can be emitted from any visitor without
+//! threading the transform context through.
+
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::rc::Rc;
+use swc_core::common::errors::DiagnosticId;
+use swc_core::common::sync::Lrc;
+use swc_core::common::{SourceMapper, Span};
+use swc_core::plugin::errors::HANDLER;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Code {
+    DuplicatePlaceholder,
+    InvalidPlaceholderName,
+    NumericPlaceholderName,
+    InvalidPlaceholderAttribute,
+    InvalidIcuSyntax,
+    UnusedPluralCategory,
+    ConflictingMessageId,
+    NestedTrans,
+    InvalidLabeledExpression,
+    InvalidPhUsage,
+    UntranslatedTernary,
+    UntranslatedPlaceholderText,
+    UnsupportedSpread,
+    MissingChoiceValue,
+    UnsupportedUseLingui,
+    InvalidDirective,
+    CustomMacroArguments,
+    InvalidCustomMacro,
+    HardcodedString,
+    InvalidChoiceCases,
+    InvalidOffset,
+    UnsupportedCaseValue,
+    MismatchedTag,
+    MissingOtherCase,
+    UnknownMacro,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Error,
+    Warning,
+    /// not reported at all, only used in overrides
+    Off,
+}
+
+impl Code {
+    pub const ALL: &'static [Code] = &[
+        Code::DuplicatePlaceholder,
+        Code::InvalidPlaceholderName,
+        Code::NumericPlaceholderName,
+        Code::InvalidPlaceholderAttribute,
+        Code::InvalidIcuSyntax,
+        Code::UnusedPluralCategory,
+        Code::ConflictingMessageId,
+        Code::NestedTrans,
+        Code::InvalidLabeledExpression,
+        Code::InvalidPhUsage,
+        Code::UntranslatedTernary,
+        Code::UntranslatedPlaceholderText,
+        Code::UnsupportedSpread,
+        Code::MissingChoiceValue,
+        Code::UnsupportedUseLingui,
+        Code::InvalidDirective,
+        Code::CustomMacroArguments,
+        Code::InvalidCustomMacro,
+        Code::HardcodedString,
+        Code::InvalidChoiceCases,
+        Code::InvalidOffset,
+        Code::UnsupportedCaseValue,
+        Code::MismatchedTag,
+        Code::MissingOtherCase,
+        Code::UnknownMacro,
+    ];
+
+    /// Code, name and default severity. Codes are never reused or renumbered.
+    fn info(&self) -> (&'static str, &'static str, Severity) {
+        use Severity::*;
+
+        match self {
+            Code::DuplicatePlaceholder => ("LINGUI001", "duplicate-placeholder", Error),
+            Code::InvalidPlaceholderName => ("LINGUI002", "invalid-placeholder-name", Error),
+            Code::NumericPlaceholderName => ("LINGUI003", "numeric-placeholder-name", Error),
+            Code::InvalidPlaceholderAttribute => {
+                ("LINGUI004", "invalid-placeholder-attribute", Error)
+            }
+            Code::InvalidIcuSyntax => ("LINGUI005", "invalid-icu-syntax", Error),
+            Code::UnusedPluralCategory => ("LINGUI006", "unused-plural-category", Warning),
+            Code::ConflictingMessageId => ("LINGUI007", "conflicting-message-id", Error),
+            Code::NestedTrans => ("LINGUI008", "nested-trans", Error),
+            Code::InvalidLabeledExpression => ("LINGUI009", "invalid-labeled-expression", Error),
+            Code::InvalidPhUsage => ("LINGUI010", "invalid-ph-usage", Error),
+            Code::UntranslatedTernary => ("LINGUI011", "untranslated-ternary", Warning),
+            Code::UntranslatedPlaceholderText => {
+                ("LINGUI012", "untranslated-placeholder-text", Warning)
+            }
+            Code::UnsupportedSpread => ("LINGUI013", "unsupported-spread", Warning),
+            Code::MissingChoiceValue => ("LINGUI014", "missing-choice-value", Warning),
+            Code::UnsupportedUseLingui => ("LINGUI015", "unsupported-use-lingui", Warning),
+            Code::InvalidDirective => ("LINGUI016", "invalid-directive", Error),
+            Code::CustomMacroArguments => ("LINGUI017", "custom-macro-arguments", Error),
+            Code::InvalidCustomMacro => ("LINGUI018", "invalid-custom-macro", Error),
+            Code::HardcodedString => ("LINGUI019", "hardcoded-string", Warning),
+            Code::InvalidChoiceCases => ("LINGUI020", "invalid-choice-cases", Error),
+            Code::InvalidOffset => ("LINGUI021", "invalid-offset", Error),
+            Code::UnsupportedCaseValue => ("LINGUI022", "unsupported-case-value", Error),
+            Code::MismatchedTag => ("LINGUI023", "mismatched-tag", Error),
+            Code::MissingOtherCase => ("LINGUI024", "missing-other-case", Warning),
+            Code::UnknownMacro => ("LINGUI025", "unknown-macro", Error),
+        }
+    }
+
+    /// Stable code, e.g. `LINGUI001`
+    pub fn id(&self) -> &'static str {
+        self.info().0
+    }
+
+    /// Stable name, e.g. `duplicate-placeholder`
+    pub fn name(&self) -> &'static str {
+        self.info().1
+    }
+
+    pub fn default_severity(&self) -> Severity {
+        self.info().2
+    }
+
+    /// Look a code up by either its code or its name
+    pub fn parse(value: &str) -> Option<Code> {
+        Code::ALL
+            .iter()
+            .copied()
+            .find(|code| code.id() == value || code.name() == value)
+    }
+}
+
+/// Codes are written as `LINGUI001` and read from either the code or the name
+impl Serialize for Code {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.id())
+    }
+}
+
+impl<'de> Deserialize<'de> for Code {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Code, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        Code::parse(&value).ok_or_else(|| D::Error::custom(format!("unknown diagnostic `{value}`")))
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DiagnosticsFormat {
+    #[default]
+    Text,
+    Json,
+}
+
+#[derive(Default)]
+pub struct DiagnosticsConfig {
+    pub format: DiagnosticsFormat,
+    /// report warnings as errors
+    pub strict: bool,
+    /// severities which replace the defaults, and `strict` for those codes
+    pub severity: BTreeMap<Code, Severity>,
+    /// used to resolve line and column in JSON output
+    pub source_map: Option<Lrc<dyn SourceMapper>>,
+    pub filename: Option<String>,
+}
+
+impl DiagnosticsConfig {
+    pub fn severity(&self, code: Code) -> Severity {
+        if let Some(severity) = self.severity.get(&code) {
+            return *severity;
+        }
+
+        match code.default_severity() {
+            Severity::Warning if self.strict => Severity::Error,
+            severity => severity,
+        }
+    }
+}
+
+thread_local! {
+    static CONFIG: RefCell<Option<Rc<DiagnosticsConfig>>> = const { RefCell::new(None) };
+}
+
+/// Run `f` with diagnostics emitted according to `config`
+pub fn with_config<R>(config: DiagnosticsConfig, f: impl FnOnce() -> R) -> R {
+    let previous = CONFIG.with(|c| c.replace(Some(Rc::new(config))));
+    let result = f();
+    CONFIG.with(|c| *c.borrow_mut() = previous);
+    result
+}
+
+fn config() -> Rc<DiagnosticsConfig> {
+    CONFIG.with(|c| c.borrow().clone()).unwrap_or_default()
+}
+
+#[must_use = "diagnostics are only reported by `emit`"]
+pub struct Diagnostic {
+    code: Code,
+    span: Span,
+    message: String,
+    help: Option<String>,
+    related: Vec<(Span, String)>,
+}
+
+impl Diagnostic {
+    pub fn new(code: Code, span: Span, message: impl Into<String>) -> Diagnostic {
+        Diagnostic {
+            code,
+            span,
+            message: message.into(),
+            help: None,
+            related: Vec::new(),
+        }
+    }
+
+    pub fn help(mut self, help: impl Into<String>) -> Diagnostic {
+        self.help = Some(help.into());
+        self
+    }
+
+    /// Point at another location which explains the diagnostic
+    pub fn related(mut self, span: Span, message: impl Into<String>) -> Diagnostic {
+        self.related.push((span, message.into()));
+        self
+    }
+
+    pub fn emit(self) {
+        let config = config();
+        let severity = config.severity(self.code);
+        if severity == Severity::Off {
+            return;
+        }
+
+        HANDLER.with(|h| {
+            if config.format == DiagnosticsFormat::Json {
+                let json = self.to_json(&config, severity);
+                match severity {
+                    Severity::Error => h.struct_err(&json).emit(),
+                    _ => h.struct_warn(&json).emit(),
+                }
+                return;
+            }
+
+            let code = DiagnosticId::Error(self.code.id().into());
+            let mut builder = match severity {
+                Severity::Error => h.struct_span_err_with_code(self.span, &self.message, code),
+                _ => h.struct_span_warn_with_code(self.span, &self.message, code),
+            };
+            for (span, message) in &self.related {
+                builder.span_note(*span, message);
+            }
+            if let Some(help) = &self.help {
+                builder.help(help);
+            }
+            builder.emit();
+        });
+    }
+
+    fn to_json(&self, config: &DiagnosticsConfig, severity: Severity) -> String {
+        let location = |span: Span| {
+            let source_map = config.source_map.as_ref().filter(|_| !span.is_dummy())?;
+            let start = source_map.lookup_char_pos(span.lo);
+            let end = source_map.lookup_char_pos(span.hi);
+
+            Some(JsonLocation {
+                file: config
+                    .filename
+                    .clone()
+                    .unwrap_or_else(|| start.file.name.to_string()),
+                line: start.line,
+                column: start.col.0 + 1,
+                end_line: end.line,
+                end_column: end.col.0 + 1,
+            })
+        };
+
+        let json = JsonDiagnostic {
+            code: self.code.id(),
+            name: self.code.name(),
+            severity,
+            message: &self.message,
+            help: self.help.as_deref(),
+            location: location(self.span),
+            related: self
+                .related
+                .iter()
+                .map(|(span, message)| JsonRelated {
+                    message,
+                    location: location(*span),
+                })
+                .collect(),
+        };
+
+        serde_json::to_string(&json).expect("diagnostics are serializable")
+    }
+}
+
+/// Shape of a diagnostic in JSON output, lines and columns are 1-based
+#[derive(Serialize)]
+struct JsonDiagnostic<'a> {
+    code: &'static str,
+    name: &'static str,
+    severity: Severity,
+    message: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    help: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    location: Option<JsonLocation>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    related: Vec<JsonRelated<'a>>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonLocation {
+    file: String,
+    line: usize,
+    column: usize,
+    end_line: usize,
+    end_column: usize,
+}
+
+#[derive(Serialize)]
+struct JsonRelated<'a> {
+    message: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    location: Option<JsonLocation>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn codes_are_unique() {
+        for (i, code) in Code::ALL.iter().enumerate() {
+            assert_eq!(Code::parse(code.id()), Some(*code));
+            assert_eq!(Code::parse(code.name()), Some(*code));
+            assert_eq!(code.id(), format!("LINGUI{:03}", i + 1));
+        }
+    }
+
+    #[test]
+    fn resolves_severity() {
+        let config = DiagnosticsConfig {
+            strict: true,
+            severity: BTreeMap::from([
+                (Code::HardcodedString, Severity::Off),
+                (Code::InvalidIcuSyntax, Severity::Warning),
+            ]),
+            ..Default::default()
+        };
+
+        assert_eq!(config.severity(Code::MissingChoiceValue), Severity::Error);
+        assert_eq!(config.severity(Code::HardcodedString), Severity::Off);
+        assert_eq!(config.severity(Code::InvalidIcuSyntax), Severity::Warning);
+        assert_eq!(
+            DiagnosticsConfig::default().severity(Code::MissingChoiceValue),
+            Severity::Warning
+        );
+    }
+}
@@ -0,0 +1,51 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use swc_core::common::Span;
+
+/// A message descriptor exactly as the macro produced it, captured in
+/// extraction mode regardless of the configured `descriptor_fields`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExtractedMessage {
+    pub id: String,
+    /// `None` when the macro only referenced an explicit id, e.g. `t({id: "foo"})`
+    pub message: Option<String>,
+    pub context: Option<String>,
+    pub comment: Option<String>,
+    /// names of the value placeholders, e.g. `name` for `{name}`
+    pub placeholders: Vec<String>,
+    /// names of the component placeholders, e.g. `0` for `<0>...</0>`
+    pub components: Vec<String>,
+    /// span of the macro invocation in the original source
+    pub origin: Span,
+}
+
+/// Shared sink for extracted messages.
+///
+/// The collector is cheap to clone and all clones write into the same buffer,
+/// so it survives the `TransformCtx` copies made for `useLingui` scopes and can
+/// be read back after the fold has finished.
+#[derive(Debug, Clone, Default)]
+pub struct MessageCollector(Rc<RefCell<Vec<ExtractedMessage>>>);
+
+impl MessageCollector {
+    pub fn new() -> MessageCollector {
+        Default::default()
+    }
+
+    pub fn push(&self, message: ExtractedMessage) {
+        self.0.borrow_mut().push(message);
+    }
+
+    /// Drain all messages collected so far, in source order of transformation.
+    pub fn take(&self) -> Vec<ExtractedMessage> {
+        std::mem::take(&mut self.0.borrow_mut())
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.borrow().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.borrow().is_empty()
+    }
+}
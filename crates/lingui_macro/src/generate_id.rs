@@ -1,16 +1,152 @@
 use data_encoding::{BASE64, BASE64URL};
-use sha2::{Digest, Sha256};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256, Sha512};
 
-pub fn generate_message_id(message: &str, context: &str, use_lingui_v5: bool) -> String {
-    let mut hasher = Sha256::new();
-    hasher.update(message.as_bytes());
-    hasher.update([0x1F]); // UNIT_SEPARATOR
-    hasher.update(context.as_bytes());
+const UNIT_SEPARATOR: u8 = 0x1F;
+
+/// How ids are generated for messages without an explicit id
+#[derive(Deserialize, Serialize, Debug, PartialEq, Clone)]
+#[serde(rename_all = "camelCase", try_from = "IdGenerationConfig")]
+pub struct IdGeneration {
+    pub strategy: IdStrategy,
+    pub algorithm: HashAlgorithm,
+    /// number of base64 characters kept from the hash, between 1 and
+    /// [`HashAlgorithm::encoded_length`]
+    pub length: usize,
+    /// mixed into the hash, so separate apps sharing a catalog don't collide
+    pub salt: Option<String>,
+}
+
+impl Default for IdGeneration {
+    fn default() -> Self {
+        Self {
+            strategy: IdStrategy::Hash,
+            algorithm: HashAlgorithm::Sha256,
+            length: 6,
+            salt: None,
+        }
+    }
+}
+
+/// [`IdGeneration`] as written in the config, `length` is checked against the algorithm
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+struct IdGenerationConfig {
+    strategy: IdStrategy,
+    algorithm: HashAlgorithm,
+    length: usize,
+    salt: Option<String>,
+}
+
+impl Default for IdGenerationConfig {
+    fn default() -> Self {
+        let IdGeneration {
+            strategy,
+            algorithm,
+            length,
+            salt,
+        } = IdGeneration::default();
+        Self {
+            strategy,
+            algorithm,
+            length,
+            salt,
+        }
+    }
+}
+
+impl TryFrom<IdGenerationConfig> for IdGeneration {
+    type Error = String;
+
+    fn try_from(config: IdGenerationConfig) -> Result<Self, Self::Error> {
+        let max = config.algorithm.encoded_length();
+        if !(1..=max).contains(&config.length) {
+            return Err(format!(
+                "invalid id length {}, expected 1 to {max} for {}",
+                config.length,
+                config.algorithm.name()
+            ));
+        }
+
+        Ok(Self {
+            strategy: config.strategy,
+            algorithm: config.algorithm,
+            length: config.length,
+            salt: config.salt,
+        })
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, PartialEq, Clone, Copy, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum IdStrategy {
+    /// base64 encoded hash of the message and context, the Lingui default
+    #[default]
+    Hash,
+    /// the source message itself, prefixed with `context\u{4}` when there is a context
+    Message,
+}
+
+#[derive(Deserialize, Serialize, Debug, PartialEq, Clone, Copy, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum HashAlgorithm {
+    #[default]
+    Sha256,
+    Sha512,
+}
+
+impl HashAlgorithm {
+    fn name(self) -> &'static str {
+        match self {
+            HashAlgorithm::Sha256 => "sha256",
+            HashAlgorithm::Sha512 => "sha512",
+        }
+    }
+
+    /// Number of base64 characters of the unpadded hash
+    pub fn encoded_length(self) -> usize {
+        let bytes = match self {
+            HashAlgorithm::Sha256 => 32,
+            HashAlgorithm::Sha512 => 64,
+        };
+        (bytes * 4usize).div_ceil(3)
+    }
+}
+
+impl IdGeneration {
+    pub fn generate(&self, message: &str, context: &str, use_lingui_v5: bool) -> String {
+        match self.strategy {
+            IdStrategy::Message if context.is_empty() => message.to_string(),
+            // same separator gettext uses between `msgctxt` and `msgid`
+            IdStrategy::Message => format!("{context}\u{4}{message}"),
+            IdStrategy::Hash => {
+                let mut input = Vec::new();
+                if let Some(salt) = &self.salt {
+                    input.extend_from_slice(salt.as_bytes());
+                    input.push(UNIT_SEPARATOR);
+                }
+                input.extend_from_slice(message.as_bytes());
+                input.push(UNIT_SEPARATOR);
+                input.extend_from_slice(context.as_bytes());
 
-    let result = hasher.finalize();
+                let digest = match self.algorithm {
+                    HashAlgorithm::Sha256 => Sha256::digest(&input).to_vec(),
+                    HashAlgorithm::Sha512 => Sha512::digest(&input).to_vec(),
+                };
 
-    let encoder = if use_lingui_v5 { BASE64 } else { BASE64URL };
-    encoder.encode(result.as_ref())[0..6].into()
+                let encoder = if use_lingui_v5 { BASE64 } else { BASE64URL };
+                let encoded = encoder.encode(&digest);
+                let encoded = encoded.trim_end_matches('=');
+                encoded[..self.length.min(encoded.len())].into()
+            }
+        }
+    }
+}
+
+/// Lingui's default id: the first 6 characters of the base64 SHA-256 of the
+/// message and context
+pub fn generate_message_id(message: &str, context: &str, use_lingui_v5: bool) -> String {
+    IdGeneration::default().generate(message, context, use_lingui_v5)
 }
 
 #[cfg(test)]
@@ -47,4 +183,76 @@ mod tests {
             "SO/WB8"
         )
     }
+
+    #[test]
+    fn test_id_generation_length_and_algorithm() {
+        let ids = IdGeneration {
+            length: 10,
+            ..Default::default()
+        };
+        assert_eq!(ids.generate("my message", "", false), "vQhkQxCjNm");
+
+        let ids = IdGeneration {
+            algorithm: HashAlgorithm::Sha512,
+            length: 86,
+            ..Default::default()
+        };
+        assert_eq!(ids.generate("my message", "", false).len(), 86);
+    }
+
+    #[test]
+    fn test_id_generation_config() {
+        let ids = serde_json::from_str::<IdGeneration>(r#"{"length": 10}"#).unwrap();
+        assert_eq!(
+            ids,
+            IdGeneration {
+                length: 10,
+                ..Default::default()
+            }
+        );
+
+        let ids = serde_json::from_str::<IdGeneration>(r#"{"algorithm": "sha512", "length": 86}"#)
+            .unwrap();
+        assert_eq!(ids.length, 86);
+    }
+
+    #[test]
+    fn test_id_generation_rejects_invalid_length() {
+        let error = serde_json::from_str::<IdGeneration>(r#"{"length": 0}"#).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "invalid id length 0, expected 1 to 43 for sha256"
+        );
+
+        let error =
+            serde_json::from_str::<IdGeneration>(r#"{"algorithm": "sha512", "length": 200}"#)
+                .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "invalid id length 200, expected 1 to 86 for sha512"
+        );
+    }
+
+    #[test]
+    fn test_id_generation_salt() {
+        let ids = IdGeneration {
+            salt: Some("checkout".into()),
+            ..Default::default()
+        };
+        assert_ne!(ids.generate("my message", "", false), "vQhkQx");
+        assert_eq!(ids.generate("my message", "", false).len(), 6);
+    }
+
+    #[test]
+    fn test_id_generation_message_as_id() {
+        let ids = IdGeneration {
+            strategy: IdStrategy::Message,
+            ..Default::default()
+        };
+        assert_eq!(ids.generate("my message", "", false), "my message");
+        assert_eq!(
+            ids.generate("my message", "ctx", false),
+            "ctx\u{4}my message"
+        );
+    }
 }
@@ -0,0 +1,172 @@
+//! Compiled message format loaded by the Lingui runtime, the same output as
+//! `compileMessage` from `@lingui/message-utils`.
+//!
+//! A message made only of text compiles to that text as a string. Any other
+//! message compiles to an array of tokens: strings, `"#"` for octothorpes,
+//! `[name]` for arguments, `[name, type, style?]` for formatted arguments and
+//! `[name, "plural" | "select" | "selectordinal", { offset?, ...cases }]` for
+//! choices, where each case is compiled the same way and `=0` is written as
+//! `0`. Date skeletons like `::yyyyMMdd` become `Intl.DateTimeFormat` options.
+
+use super::{date_options, parse, DateOptions, ParseError, SelectKind, Token};
+use serde::ser::{SerializeMap, SerializeSeq};
+use serde::{Serialize, Serializer};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CompiledToken {
+    Text(String),
+    Octothorpe,
+    Argument(String),
+    Function {
+        arg: String,
+        key: String,
+        style: Option<CompiledStyle>,
+    },
+    Select {
+        arg: String,
+        kind: SelectKind,
+        offset: Option<i64>,
+        cases: Vec<(String, CompiledMessage)>,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CompiledStyle {
+    /// passed to the formatter as is, e.g. `percent` or `short`
+    Text(String),
+    /// converted from a `::` date skeleton
+    DateOptions(DateOptions),
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct CompiledMessage(pub Vec<CompiledToken>);
+
+fn compile_style(key: &str, style: &str) -> CompiledStyle {
+    // skeletons are validated by the parser
+    style
+        .strip_prefix("::")
+        .filter(|_| key == "date")
+        .and_then(|skeleton| date_options(skeleton).ok())
+        .map_or_else(
+            || CompiledStyle::Text(style.to_string()),
+            CompiledStyle::DateOptions,
+        )
+}
+
+pub fn compile(tokens: &[Token]) -> CompiledMessage {
+    CompiledMessage(
+        tokens
+            .iter()
+            .map(|token| match token {
+                Token::Content(text) => CompiledToken::Text(text.clone()),
+                Token::Octothorpe => CompiledToken::Octothorpe,
+                Token::Argument(arg) => CompiledToken::Argument(arg.clone()),
+                Token::Function { arg, key, style } => CompiledToken::Function {
+                    arg: arg.clone(),
+                    key: key.clone(),
+                    style: style.as_deref().map(|style| compile_style(key, style)),
+                },
+                Token::Select {
+                    arg,
+                    kind,
+                    offset,
+                    cases,
+                } => CompiledToken::Select {
+                    arg: arg.clone(),
+                    kind: *kind,
+                    offset: *offset,
+                    cases: cases
+                        .iter()
+                        .map(|case| {
+                            let key = case.key.strip_prefix('=').unwrap_or(&case.key);
+                            (key.to_string(), compile(&case.tokens))
+                        })
+                        .collect(),
+                },
+            })
+            .collect(),
+    )
+}
+
+pub fn compile_message(message: &str) -> Result<CompiledMessage, ParseError> {
+    Ok(compile(&parse(message)?))
+}
+
+impl Serialize for CompiledMessage {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let text: Option<String> = self
+            .0
+            .iter()
+            .map(|token| match token {
+                CompiledToken::Text(text) => Some(text.as_str()),
+                _ => None,
+            })
+            .collect();
+
+        match text {
+            Some(text) => serializer.serialize_str(&text),
+            None => serializer.collect_seq(&self.0),
+        }
+    }
+}
+
+impl Serialize for CompiledStyle {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            CompiledStyle::Text(style) => serializer.serialize_str(style),
+            CompiledStyle::DateOptions(options) => {
+                let mut map = serializer.serialize_map(Some(options.len()))?;
+                for (key, value) in options {
+                    map.serialize_entry(key, value)?;
+                }
+                map.end()
+            }
+        }
+    }
+}
+
+struct Cases<'a>(Option<i64>, &'a [(String, CompiledMessage)]);
+
+impl Serialize for Cases<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(None)?;
+        if let Some(offset) = self.0 {
+            map.serialize_entry("offset", &offset)?;
+        }
+        for (key, message) in self.1 {
+            map.serialize_entry(key, message)?;
+        }
+        map.end()
+    }
+}
+
+impl Serialize for CompiledToken {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            CompiledToken::Text(text) => serializer.serialize_str(text),
+            CompiledToken::Octothorpe => serializer.serialize_str("#"),
+            CompiledToken::Argument(arg) => serializer.collect_seq([arg]),
+            CompiledToken::Function { arg, key, style } => {
+                let mut seq = serializer.serialize_seq(None)?;
+                seq.serialize_element(arg)?;
+                seq.serialize_element(key)?;
+                if let Some(style) = style {
+                    seq.serialize_element(style)?;
+                }
+                seq.end()
+            }
+            CompiledToken::Select {
+                arg,
+                kind,
+                offset,
+                cases,
+            } => {
+                let mut seq = serializer.serialize_seq(Some(3))?;
+                seq.serialize_element(arg)?;
+                seq.serialize_element(kind.as_str())?;
+                seq.serialize_element(&Cases(*offset, cases))?;
+                seq.end()
+            }
+        }
+    }
+}
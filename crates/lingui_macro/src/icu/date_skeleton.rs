@@ -0,0 +1,98 @@
+//! ICU date skeletons such as `{d, date, ::yyyyMMMd}`, converted to the
+//! `Intl.DateTimeFormat` options the Lingui runtime formats dates with, the
+//! same as `@messageformat/date-skeleton` does for Lingui's `compileMessage`.
+//!
+//! Each field is a run of the same letter, its width picks the style, e.g.
+//! `MM` is a 2-digit month and `MMMM` the full month name.
+
+/// Options in skeleton order, as `(option, value)`
+pub type DateOptions = Vec<(&'static str, &'static str)>;
+
+/// Convert a skeleton without the leading `::`. Errors carry the byte offset
+/// of the offending field within the skeleton.
+pub fn date_options(skeleton: &str) -> Result<DateOptions, (usize, String)> {
+    let mut options: DateOptions = Vec::new();
+    let mut fields: Vec<char> = Vec::new();
+    let mut rest = skeleton.char_indices().peekable();
+
+    while let Some((offset, c)) = rest.next() {
+        let mut width = 1;
+        while rest.next_if(|(_, next)| *next == c).is_some() {
+            width += 1;
+        }
+
+        let field = match c {
+            'L' => 'M',
+            'c' | 'e' => 'E',
+            'H' | 'K' | 'k' | 'j' => 'h',
+            _ => c,
+        };
+        if fields.contains(&field) {
+            return Err((offset, format!("Duplicate `{c}` field in date skeleton")));
+        }
+        fields.push(field);
+
+        let option = field_option(c, width).ok_or_else(|| {
+            (
+                offset,
+                format!(
+                    "Unsupported date skeleton field `{}`",
+                    c.to_string().repeat(width)
+                ),
+            )
+        })?;
+        options.extend(option);
+    }
+
+    Ok(options)
+}
+
+fn field_option(c: char, width: usize) -> Option<DateOptions> {
+    let text = |width| match width {
+        1..=3 => Some("short"),
+        4 => Some("long"),
+        5 => Some("narrow"),
+        _ => None,
+    };
+    let numeric = |width| match width {
+        1 => Some("numeric"),
+        2 => Some("2-digit"),
+        _ => None,
+    };
+    let hour = |cycle: Option<&'static str>| {
+        let mut options = vec![("hour", numeric(width)?)];
+        options.extend(cycle.map(|cycle| ("hourCycle", cycle)));
+        Some(options)
+    };
+
+    match c {
+        'G' => Some(vec![("era", text(width)?)]),
+        'y' => Some(vec![(
+            "year",
+            if width == 2 { "2-digit" } else { "numeric" },
+        )]),
+        'M' | 'L' => {
+            let month =
+                ["numeric", "2-digit", "short", "long", "narrow"].get(width.checked_sub(1)?)?;
+            Some(vec![("month", *month)])
+        }
+        'd' => Some(vec![("day", numeric(width)?)]),
+        'E' => Some(vec![("weekday", text(width)?)]),
+        // numeric weekdays have no `Intl` equivalent
+        'c' | 'e' if width >= 3 => Some(vec![("weekday", text(width)?)]),
+        // the day period follows from the hour cycle
+        'a' => Some(vec![]),
+        'h' => hour(Some("h12")),
+        'H' => hour(Some("h23")),
+        'K' => hour(Some("h11")),
+        'k' => hour(Some("h24")),
+        'j' => hour(None),
+        'm' => Some(vec![("minute", numeric(width)?)]),
+        's' => Some(vec![("second", numeric(width)?)]),
+        'z' => Some(vec![(
+            "timeZoneName",
+            if width < 4 { "short" } else { "long" },
+        )]),
+        _ => None,
+    }
+}
@@ -0,0 +1,356 @@
+//! ICU MessageFormat parser following `@messageformat/parser`, which is what
+//! the Lingui runtime and CLI use.
+//!
+//! Text is unescaped with the ICU apostrophe rules (`''` is a literal quote,
+//! `'{…}'` quotes syntax characters) and `#` is only special inside `plural`
+//! and `selectordinal` cases. JSX component tags like `<0>…</0>` are plain text
+//! at this level.
+
+mod compile;
+mod date_skeleton;
+mod pseudo;
+mod tag;
+
+pub use compile::{compile, compile_message, CompiledMessage, CompiledStyle, CompiledToken};
+pub use date_skeleton::{date_options, DateOptions};
+pub use pseudo::pseudo_localize;
+pub use tag::{is_valid_placeholder_name, read_tag, TagKind};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Token {
+    Content(String),
+    /// `#` inside a plural case
+    Octothorpe,
+    /// `{name}`
+    Argument(String),
+    /// `{value, number}` or `{value, date, short}`
+    Function {
+        arg: String,
+        key: String,
+        style: Option<String>,
+    },
+    /// `{count, plural, …}`, `{count, selectordinal, …}` or `{value, select, …}`
+    Select {
+        arg: String,
+        kind: SelectKind,
+        offset: Option<i64>,
+        cases: Vec<SelectCase>,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SelectKind {
+    Plural,
+    SelectOrdinal,
+    Select,
+}
+
+impl SelectKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SelectKind::Plural => "plural",
+            SelectKind::SelectOrdinal => "selectordinal",
+            SelectKind::Select => "select",
+        }
+    }
+
+    fn from_key(key: &str) -> Option<SelectKind> {
+        match key {
+            "plural" => Some(SelectKind::Plural),
+            "selectordinal" => Some(SelectKind::SelectOrdinal),
+            "select" => Some(SelectKind::Select),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SelectCase {
+    pub key: String,
+    pub tokens: Vec<Token>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// byte offset in the message
+    pub offset: usize,
+    pub message: String,
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} (at offset {})", self.message, self.offset)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+pub fn parse(message: &str) -> Result<Vec<Token>, ParseError> {
+    let mut parser = Parser {
+        src: message,
+        pos: 0,
+    };
+    parser.parse_body(false, None)
+}
+
+struct Parser<'a> {
+    src: &'a str,
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<char> {
+        self.src[self.pos..].chars().next()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += c.len_utf8();
+        Some(c)
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.bump();
+        }
+    }
+
+    fn read_while(&mut self, pred: impl Fn(char) -> bool) -> &str {
+        let start = self.pos;
+        while self.peek().is_some_and(&pred) {
+            self.bump();
+        }
+        &self.src[start..self.pos]
+    }
+
+    fn error<T>(&self, offset: usize, message: impl Into<String>) -> Result<T, ParseError> {
+        Err(ParseError {
+            offset,
+            message: message.into(),
+        })
+    }
+
+    /// Parse message text until the end of input, or until the `}` closing
+    /// the case which started at `case_start`.
+    fn parse_body(
+        &mut self,
+        in_plural: bool,
+        case_start: Option<usize>,
+    ) -> Result<Vec<Token>, ParseError> {
+        let mut tokens = Vec::new();
+        let mut text = String::new();
+
+        fn flush(text: &mut String, tokens: &mut Vec<Token>) {
+            if !text.is_empty() {
+                tokens.push(Token::Content(std::mem::take(text)));
+            }
+        }
+
+        loop {
+            let Some(c) = self.peek() else {
+                if let Some(start) = case_start {
+                    return self.error(start, "Unterminated case, expected `}`");
+                }
+                break;
+            };
+
+            match c {
+                '{' => {
+                    flush(&mut text, &mut tokens);
+                    tokens.push(self.parse_argument(in_plural)?);
+                }
+                '}' => {
+                    if case_start.is_some() {
+                        break;
+                    }
+                    return self.error(self.pos, "Unexpected `}`");
+                }
+                '#' if in_plural => {
+                    flush(&mut text, &mut tokens);
+                    self.bump();
+                    tokens.push(Token::Octothorpe);
+                }
+                '\'' => {
+                    self.bump();
+                    self.parse_quoted(in_plural, &mut text);
+                }
+                _ => {
+                    self.bump();
+                    text.push(c);
+                }
+            }
+        }
+
+        flush(&mut text, &mut tokens);
+        Ok(tokens)
+    }
+
+    /// Handle text after an apostrophe. It only starts a quoted literal when
+    /// followed by a syntax character, otherwise it is a literal apostrophe.
+    fn parse_quoted(&mut self, in_plural: bool, text: &mut String) {
+        match self.peek() {
+            Some('\'') => {
+                self.bump();
+                text.push('\'');
+            }
+            Some('{' | '}') => self.read_quoted(text),
+            Some('#') if in_plural => self.read_quoted(text),
+            _ => text.push('\''),
+        }
+    }
+
+    fn read_quoted(&mut self, text: &mut String) {
+        while let Some(c) = self.bump() {
+            if c != '\'' {
+                text.push(c);
+            } else if self.peek() == Some('\'') {
+                self.bump();
+                text.push('\'');
+            } else {
+                return;
+            }
+        }
+    }
+
+    fn parse_argument(&mut self, in_plural: bool) -> Result<Token, ParseError> {
+        let start = self.pos;
+        self.bump();
+        self.skip_whitespace();
+
+        let arg = self
+            .read_while(|c| !c.is_whitespace() && !matches!(c, '{' | '}' | ',' | '#' | '\''))
+            .to_string();
+        if arg.is_empty() {
+            return self.error(self.pos, "Expected an argument name");
+        }
+        self.skip_whitespace();
+
+        match self.bump() {
+            Some('}') => return Ok(Token::Argument(arg)),
+            Some(',') => {}
+            None => return self.error(start, "Unterminated argument, expected `}`"),
+            Some(_) => return self.error(self.pos - 1, "Expected `,` or `}` after argument name"),
+        }
+        self.skip_whitespace();
+
+        let key = self
+            .read_while(|c| c.is_alphanumeric() || c == '_')
+            .to_string();
+        if key.is_empty() {
+            return self.error(self.pos, "Expected an argument type");
+        }
+        self.skip_whitespace();
+
+        if let Some(kind) = SelectKind::from_key(&key) {
+            if self.bump() != Some(',') {
+                return self.error(start, format!("Expected `,` and cases after `{key}`"));
+            }
+            let in_plural = in_plural || kind != SelectKind::Select;
+            return self.parse_cases(arg, kind, in_plural, start);
+        }
+
+        let style = match self.bump() {
+            Some('}') => None,
+            Some(',') => {
+                let style_start = self.pos;
+                let mut depth = 0;
+                loop {
+                    match self.peek() {
+                        None => return self.error(start, "Unterminated argument, expected `}`"),
+                        Some('{') => depth += 1,
+                        Some('}') if depth == 0 => break,
+                        Some('}') => depth -= 1,
+                        Some(_) => {}
+                    }
+                    self.bump();
+                }
+                let raw = &self.src[style_start..self.pos];
+                let style = raw.trim().to_string();
+                if let Some(skeleton) = style.strip_prefix("::").filter(|_| key == "date") {
+                    let skeleton_start = style_start + (raw.len() - raw.trim_start().len()) + 2;
+                    if let Err((offset, message)) = date_options(skeleton) {
+                        return self.error(skeleton_start + offset, message);
+                    }
+                }
+                self.bump();
+                Some(style).filter(|s| !s.is_empty())
+            }
+            None => return self.error(start, "Unterminated argument, expected `}`"),
+            Some(_) => return self.error(self.pos - 1, "Expected `,` or `}` after argument type"),
+        };
+
+        Ok(Token::Function { arg, key, style })
+    }
+
+    fn parse_cases(
+        &mut self,
+        arg: String,
+        kind: SelectKind,
+        in_plural: bool,
+        start: usize,
+    ) -> Result<Token, ParseError> {
+        let mut offset = None;
+        let mut cases: Vec<SelectCase> = Vec::new();
+
+        loop {
+            self.skip_whitespace();
+
+            match self.peek() {
+                None => return self.error(start, "Unterminated argument, expected `}`"),
+                Some('}') => {
+                    self.bump();
+                    break;
+                }
+                _ => {}
+            }
+
+            if kind != SelectKind::Select
+                && cases.is_empty()
+                && offset.is_none()
+                && self.src[self.pos..].starts_with("offset:")
+            {
+                self.pos += "offset:".len();
+                self.skip_whitespace();
+                let number_start = self.pos;
+                let number = self.read_while(|c| c.is_ascii_digit() || c == '-');
+                offset =
+                    Some(number.parse().or_else(|_| {
+                        self.error(number_start, "Expected a number after `offset:`")
+                    })?);
+                continue;
+            }
+
+            let key_start = self.pos;
+            let key = self
+                .read_while(|c| !c.is_whitespace() && !matches!(c, '{' | '}'))
+                .to_string();
+            if key.is_empty() {
+                return self.error(key_start, "Expected a case key");
+            }
+            if cases.iter().any(|case| case.key == key) {
+                return self.error(key_start, format!("Duplicate case `{key}`"));
+            }
+            self.skip_whitespace();
+
+            if self.peek() != Some('{') {
+                return self.error(self.pos, format!("Expected `{{` after case `{key}`"));
+            }
+            let case_start = self.pos;
+            self.bump();
+            let tokens = self.parse_body(in_plural, Some(case_start))?;
+            self.bump();
+
+            cases.push(SelectCase { key, tokens });
+        }
+
+        Ok(Token::Select {
+            arg,
+            kind,
+            offset,
+            cases,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests;
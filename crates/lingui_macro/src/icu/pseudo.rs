@@ -0,0 +1,148 @@
+//! Pseudo-localization of source messages, for QA builds that should show
+//! untranslated and truncated UI without waiting for real translations.
+//!
+//! Works on the parsed message rather than on macro tokens because messages
+//! written as plain strings (e.g. `t({ message: "{n, plural, …}" })`) carry
+//! ICU syntax inside a single string token. Only text is rewritten: letters
+//! get accented, every text segment is padded by about a third of its length
+//! and the whole message is wrapped in `[` `]`. Arguments, choice syntax, `#`
+//! and component tags like `<0>`, `</0>` or `<br/>` are kept as they are.
+
+use super::{parse, read_tag, ParseError, SelectCase, SelectKind, Token};
+
+const PADDING: char = '~';
+
+pub fn pseudo_localize(message: &str) -> Result<String, ParseError> {
+    let tokens = parse(message)?;
+    let mut out = String::from("[");
+    print_tokens(&tokens, false, &mut out);
+    out.push(']');
+    Ok(out)
+}
+
+fn print_tokens(tokens: &[Token], in_plural: bool, out: &mut String) {
+    for token in tokens {
+        match token {
+            Token::Content(text) => print_text(text, in_plural, out),
+            Token::Octothorpe => out.push('#'),
+            Token::Argument(arg) => {
+                out.push('{');
+                out.push_str(arg);
+                out.push('}');
+            }
+            Token::Function { arg, key, style } => {
+                out.push_str(&format!("{{{arg}, {key}"));
+                if let Some(style) = style {
+                    out.push_str(&format!(", {style}"));
+                }
+                out.push('}');
+            }
+            Token::Select {
+                arg,
+                kind,
+                offset,
+                cases,
+            } => {
+                out.push_str(&format!("{{{arg}, {},", kind.as_str()));
+                if let Some(offset) = offset {
+                    out.push_str(&format!(" offset:{offset}"));
+                }
+                let in_plural = in_plural || *kind != SelectKind::Select;
+                for SelectCase { key, tokens } in cases {
+                    out.push_str(&format!(" {key} {{"));
+                    print_tokens(tokens, in_plural, out);
+                    out.push('}');
+                }
+                out.push('}');
+            }
+        }
+    }
+}
+
+/// Accent and pad a text segment, copying component tags verbatim and
+/// re-escaping the characters which are syntax in ICU.
+fn print_text(text: &str, in_plural: bool, out: &mut String) {
+    let mut letters: usize = 0;
+    let mut rest = text;
+
+    while let Some(c) = rest.chars().next() {
+        if let Some((len, _, _)) = read_tag(rest) {
+            out.push_str(&rest[..len]);
+            rest = &rest[len..];
+            continue;
+        }
+
+        match c {
+            '\'' => out.push_str("''"),
+            '{' | '}' => out.push_str(&format!("'{c}'")),
+            '#' if in_plural => out.push_str("'#'"),
+            _ => {
+                if c.is_alphabetic() {
+                    letters += 1;
+                }
+                out.push(accent(c));
+            }
+        }
+        rest = &rest[c.len_utf8()..];
+    }
+
+    out.extend(std::iter::repeat_n(PADDING, letters.div_ceil(3)));
+}
+
+fn accent(c: char) -> char {
+    match c {
+        'a' => 'á',
+        'b' => 'ƀ',
+        'c' => 'ç',
+        'd' => 'ð',
+        'e' => 'é',
+        'f' => 'ƒ',
+        'g' => 'ĝ',
+        'h' => 'ĥ',
+        'i' => 'í',
+        'j' => 'ĵ',
+        'k' => 'ķ',
+        'l' => 'ļ',
+        'm' => 'ɱ',
+        'n' => 'ñ',
+        'o' => 'ö',
+        'p' => 'þ',
+        'q' => 'ǫ',
+        'r' => 'ŕ',
+        's' => 'š',
+        't' => 'ţ',
+        'u' => 'û',
+        'v' => 'ṽ',
+        'w' => 'ŵ',
+        'x' => 'ẋ',
+        'y' => 'ý',
+        'z' => 'ž',
+        'A' => 'Å',
+        'B' => 'Ɓ',
+        'C' => 'Ç',
+        'D' => 'Ð',
+        'E' => 'É',
+        'F' => 'Ƒ',
+        'G' => 'Ĝ',
+        'H' => 'Ĥ',
+        'I' => 'Î',
+        'J' => 'Ĵ',
+        'K' => 'Ķ',
+        'L' => 'Ļ',
+        'M' => 'Ṁ',
+        'N' => 'Ñ',
+        'O' => 'Ö',
+        'P' => 'Þ',
+        'Q' => 'Ǫ',
+        'R' => 'Ŕ',
+        'S' => 'Š',
+        'T' => 'Ţ',
+        'U' => 'Û',
+        'V' => 'Ṽ',
+        'W' => 'Ŵ',
+        'X' => 'Ẋ',
+        'Y' => 'Ý',
+        'Z' => 'Ž',
+        _ => c,
+    }
+}
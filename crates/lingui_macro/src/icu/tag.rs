@@ -0,0 +1,74 @@
+//! JSX component tags inside messages, `<0>…</0>`, `<1/>` or named ones like
+//! `<help-link>` from the `_t` attribute. They are plain text for the ICU parser,
+//! tools which rewrite message text use this scanner to leave them untouched.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TagKind {
+    Opening,
+    Closing,
+    SelfClosing,
+}
+
+/// Names accepted for named placeholders: an identifier which may contain `.`
+/// and `-`, but not start or end with them
+pub fn is_valid_placeholder_name(s: &str) -> bool {
+    if s.is_empty() {
+        return false;
+    }
+
+    let bytes = s.as_bytes();
+    let first = bytes[0];
+    if !(first.is_ascii_alphabetic() || first == b'_') {
+        return false;
+    }
+
+    if bytes.len() == 1 {
+        return true;
+    }
+
+    let last = bytes[bytes.len() - 1];
+    if !(last.is_ascii_alphanumeric() || last == b'_') {
+        return false;
+    }
+
+    bytes[1..bytes.len() - 1]
+        .iter()
+        .all(|&b| b.is_ascii_alphanumeric() || b == b'_' || b == b'.' || b == b'-')
+}
+
+/// Read a tag like `<0>`, `</help-link>` or `<0/>` at the start of `input`.
+/// Returns the tag length, the component name and the kind of tag.
+pub fn read_tag(input: &str) -> Option<(usize, &str, TagKind)> {
+    let rest = input.strip_prefix('<')?;
+    let (rest, closing) = match rest.strip_prefix('/') {
+        Some(rest) => (rest, true),
+        None => (rest, false),
+    };
+
+    let name_len = rest
+        .find(|c: char| !(c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '-')))
+        .unwrap_or(rest.len());
+    let name = &rest[..name_len];
+    let numeric = !name.is_empty() && name.bytes().all(|b| b.is_ascii_digit());
+    if !numeric && !is_valid_placeholder_name(name) {
+        return None;
+    }
+
+    let after = &rest[name_len..];
+    let (kind, tail) = if after.starts_with("/>") && !closing {
+        (TagKind::SelfClosing, 2)
+    } else if after.starts_with('>') {
+        (
+            if closing {
+                TagKind::Closing
+            } else {
+                TagKind::Opening
+            },
+            1,
+        )
+    } else {
+        return None;
+    };
+
+    Some((input.len() - after.len() + tail, name, kind))
+}
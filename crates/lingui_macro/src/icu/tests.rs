@@ -0,0 +1,268 @@
+use super::*;
+
+fn compiled(message: &str) -> String {
+    serde_json::to_string(&compile_message(message).unwrap()).unwrap()
+}
+
+fn error(message: &str) -> ParseError {
+    parse(message).unwrap_err()
+}
+
+// ---------------------------------------------------------------------------
+// parse
+// ---------------------------------------------------------------------------
+
+#[test]
+fn parse_plain_text() {
+    assert_eq!(
+        parse("Hello <0>world</0>").unwrap(),
+        vec![Token::Content("Hello <0>world</0>".into())]
+    );
+}
+
+#[test]
+fn parse_arguments_and_functions() {
+    assert_eq!(
+        parse("{ name } has {count, number} {d, date, ::yyyyMMdd}").unwrap(),
+        vec![
+            Token::Argument("name".into()),
+            Token::Content(" has ".into()),
+            Token::Function {
+                arg: "count".into(),
+                key: "number".into(),
+                style: None,
+            },
+            Token::Content(" ".into()),
+            Token::Function {
+                arg: "d".into(),
+                key: "date".into(),
+                style: Some("::yyyyMMdd".into()),
+            },
+        ]
+    );
+}
+
+#[test]
+fn parse_plural_with_offset() {
+    assert_eq!(
+        parse("{count, plural, offset:1 =0 {none} other {# items}}").unwrap(),
+        vec![Token::Select {
+            arg: "count".into(),
+            kind: SelectKind::Plural,
+            offset: Some(1),
+            cases: vec![
+                SelectCase {
+                    key: "=0".into(),
+                    tokens: vec![Token::Content("none".into())],
+                },
+                SelectCase {
+                    key: "other".into(),
+                    tokens: vec![Token::Octothorpe, Token::Content(" items".into())],
+                },
+            ],
+        }]
+    );
+}
+
+#[test]
+fn parse_octothorpe_only_inside_plural() {
+    assert_eq!(
+        parse("#1 {g, select, other {#}}").unwrap(),
+        vec![
+            Token::Content("#1 ".into()),
+            Token::Select {
+                arg: "g".into(),
+                kind: SelectKind::Select,
+                offset: None,
+                cases: vec![SelectCase {
+                    key: "other".into(),
+                    tokens: vec![Token::Content("#".into())],
+                }],
+            },
+        ]
+    );
+}
+
+#[test]
+fn parse_octothorpe_in_select_nested_in_plural() {
+    let tokens = parse("{n, plural, other {{g, select, other {#}}}}").unwrap();
+    let Token::Select { cases, .. } = &tokens[0] else {
+        panic!("expected plural");
+    };
+    let Token::Select { cases, .. } = &cases[0].tokens[0] else {
+        panic!("expected select");
+    };
+
+    assert_eq!(cases[0].tokens, vec![Token::Octothorpe]);
+}
+
+#[test]
+fn parse_apostrophe_escapes() {
+    assert_eq!(
+        parse("It''s '{name}' and don't {n, plural, other {'#'}}").unwrap(),
+        vec![
+            Token::Content("It's {name} and don't ".into()),
+            Token::Select {
+                arg: "n".into(),
+                kind: SelectKind::Plural,
+                offset: None,
+                cases: vec![SelectCase {
+                    key: "other".into(),
+                    tokens: vec![Token::Content("#".into())],
+                }],
+            },
+        ]
+    );
+}
+
+#[test]
+fn parse_choice_without_other() {
+    // reported as a warning by the macro, the runtime renders nothing for unmatched values
+    assert_eq!(
+        parse("{count, plural, one {#}}").unwrap(),
+        vec![Token::Select {
+            arg: "count".into(),
+            kind: SelectKind::Plural,
+            offset: None,
+            cases: vec![SelectCase {
+                key: "one".into(),
+                tokens: vec![Token::Octothorpe],
+            }],
+        }]
+    );
+}
+
+#[test]
+fn parse_errors() {
+    assert_eq!(
+        error("{count, plural, one {#} other {#}"),
+        ParseError {
+            offset: 0,
+            message: "Unterminated argument, expected `}`".into()
+        }
+    );
+    assert_eq!(error("Hello {name").offset, 6);
+    assert_eq!(error("Hello }").message, "Unexpected `}`");
+    assert_eq!(error("{}").message, "Expected an argument name");
+    assert_eq!(
+        error("{n, plural, one {a} one {b} other {c}}").message,
+        "Duplicate case `one`"
+    );
+    assert_eq!(
+        error("{n, plural, other}").message,
+        "Expected `{` after case `other`"
+    );
+    assert_eq!(
+        error("{n, plural, other {a}").message,
+        "Unterminated argument, expected `}`"
+    );
+    assert_eq!(
+        error("{n, select}").message,
+        "Expected `,` and cases after `select`"
+    );
+}
+
+// ---------------------------------------------------------------------------
+// compile
+// ---------------------------------------------------------------------------
+
+#[test]
+fn compile_plain_text() {
+    assert_eq!(compiled("Hello world"), r#""Hello world""#);
+    assert_eq!(compiled("It''s '{literal}'"), r#""It's {literal}""#);
+    assert_eq!(compiled(""), r#""""#);
+}
+
+#[test]
+fn compile_arguments() {
+    assert_eq!(
+        compiled("Hello {name}, {n, number, percent} {n, number}"),
+        r#"["Hello ",["name"],", ",["n","number","percent"]," ",["n","number"]]"#
+    );
+}
+
+#[test]
+fn compile_choices() {
+    assert_eq!(
+        compiled("{count, plural, offset:1 =0 {No books} one {# book} other {{gender, select, male {his} other {their}} # books}}"),
+        r##"[["count","plural",{"offset":1,"0":"No books","one":["#"," book"],"other":[["gender","select",{"male":"his","other":"their"}]," ","#"," books"]}]]"##
+    );
+    assert_eq!(
+        compiled("{n, selectordinal, one {#st} other {#th}}"),
+        r##"[["n","selectordinal",{"one":["#","st"],"other":["#","th"]}]]"##
+    );
+}
+
+#[test]
+fn compile_date_skeletons() {
+    assert_eq!(
+        compiled("{d, date, ::yyyyMMdd} {d, date, ::EEEEMMMMd} {d, time, ::hmm} {d, date, short}"),
+        r#"[["d","date",{"year":"numeric","month":"2-digit","day":"2-digit"}]," ",["d","date",{"weekday":"long","month":"long","day":"numeric"}]," ",["d","time","::hmm"]," ",["d","date","short"]]"#
+    );
+    assert_eq!(
+        compiled("{t, date, ::Hmm}"),
+        r#"[["t","date",{"hour":"numeric","hourCycle":"h23","minute":"2-digit"}]]"#
+    );
+}
+
+#[test]
+fn parse_invalid_date_skeletons() {
+    assert_eq!(
+        error("{d, date, ::yyyyQQ}"),
+        ParseError {
+            offset: 16,
+            message: "Unsupported date skeleton field `QQ`".into()
+        }
+    );
+    assert_eq!(
+        error("{d, date, ::MMMMMM}").message,
+        "Unsupported date skeleton field `MMMMMM`"
+    );
+    assert_eq!(
+        error("{d, date, ::dMd}").message,
+        "Duplicate `d` field in date skeleton"
+    );
+}
+
+// ---------------------------------------------------------------------------
+// pseudo_localize
+// ---------------------------------------------------------------------------
+
+#[test]
+fn pseudo_localize_text() {
+    assert_eq!(pseudo_localize("Hello").unwrap(), "[Ĥéļļö~~]");
+    assert_eq!(pseudo_localize("").unwrap(), "[]");
+}
+
+#[test]
+fn pseudo_localize_keeps_arguments_and_tags() {
+    assert_eq!(
+        pseudo_localize("Hi {name}, read <0>the docs</0><1/>{d, date, short}").unwrap(),
+        "[Ĥí ~{name}, ŕéáð <0>ţĥé ðöçš</0><1/>~~~~{d, date, short}]"
+    );
+}
+
+#[test]
+fn pseudo_localize_keeps_named_tags() {
+    assert_eq!(
+        pseudo_localize("See <help-link>the docs</help-link><icon.close/>").unwrap(),
+        "[Šéé <help-link>ţĥé ðöçš</help-link><icon.close/>~~~~]"
+    );
+    assert_eq!(pseudo_localize("<-x> <a.>").unwrap(), "[<-ẋ> <á.>~]");
+}
+
+#[test]
+fn pseudo_localize_keeps_choices() {
+    assert_eq!(
+        pseudo_localize("{count, plural, offset:1 =0 {No books} other {# books '#'}}").unwrap(),
+        "[{count, plural, offset:1 =0 {Ñö ƀööķš~~~} other {# ƀööķš '#'~~}}]"
+    );
+}
+
+#[test]
+fn pseudo_localize_escapes_syntax() {
+    let pseudo = pseudo_localize("It''s '{literal}'").unwrap();
+
+    assert_eq!(pseudo, "[Îţ''š '{'ļíţéŕáļ'}'~~~~]");
+    assert!(parse(&pseudo).is_ok());
+}
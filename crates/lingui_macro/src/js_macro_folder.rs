@@ -1,7 +1,7 @@
 use crate::ast_utils::*;
 use crate::builder::MessageBuilder;
 use crate::comment_directive::DirectiveValues;
-use crate::generate_id::generate_message_id;
+use crate::extract::ExtractedMessage;
 use crate::macro_utils::*;
 use crate::tokens::MsgToken;
 use swc_core::common::comments::Comments;
@@ -35,25 +35,40 @@ where
         &mut self,
         tokens: Vec<MsgToken>,
         span: Span,
+        origin: Span,
         defaults: Option<&DirectiveValues>,
     ) -> Expr {
         let parsed = MessageBuilder::parse(tokens, &self.ctx.options);
+        self.ctx.validate_message(&parsed.message_str, origin);
 
         let context = defaults
             .and_then(|defaults| defaults.context.as_deref())
             .unwrap_or_default();
 
-        let mut props: Vec<PropOrSpread> = vec![create_key_value_prop(
-            "id",
-            generate_message_id(
-                &parsed.message_str,
-                context,
-                self.ctx.options.use_lingui_v5_id_generation,
-            )
-            .into(),
-        )];
-
-        if self.ctx.options.descriptor_fields.should_keep_message() {
+        let id = self
+            .ctx
+            .options
+            .generate_message_id(&parsed.message_str, context);
+
+        self.ctx.collect_message(ExtractedMessage {
+            id: id.clone(),
+            message: Some(parsed.message_str.clone()),
+            context: defaults.and_then(|defaults| defaults.context.clone()),
+            comment: defaults.and_then(|defaults| defaults.comment.clone()),
+            placeholders: parsed.placeholders,
+            components: parsed.component_names,
+            origin,
+        });
+
+        let inlined = self
+            .ctx
+            .message_override(Some(&id), Some(&parsed.message_str));
+
+        let mut props: Vec<PropOrSpread> = vec![create_key_value_prop("id", id.into())];
+
+        if let Some(inlined) = inlined {
+            props.push(create_key_value_prop("message", inlined));
+        } else if self.ctx.options.descriptor_fields.should_keep_message() {
             props.push(create_key_value_prop("message", parsed.message));
         }
 
@@ -73,6 +88,8 @@ where
             }
         }
 
+        props.extend(self.ctx.origin_prop(origin));
+
         let message_descriptor = Expr::Object(ObjectLit { span, props });
 
         add_i18n_comment(self.comments, span, self.ctx.options.use_jsdoc_i18n_comment);
@@ -91,6 +108,7 @@ where
         let message_descriptor = Box::new(self.create_message_descriptor_from_tokens(
             tokens,
             msg_dscrptr_span,
+            call_expr_span,
             defaults.as_ref(),
         ));
 
@@ -126,7 +144,12 @@ where
     }
 
     // take {message: "", id: "", ...} object literal, process message and return updated props
-    fn update_msg_descriptor_props(&mut self, expr: Box<Expr>, span: Span) -> Box<Expr> {
+    fn update_msg_descriptor_props(
+        &mut self,
+        expr: Box<Expr>,
+        span: Span,
+        origin: Span,
+    ) -> Box<Expr> {
         if let Expr::Object(obj) = *expr {
             let defaults = self.ctx.get_comment_directive(span.lo).cloned();
             let id_prop = get_object_prop(&obj.props, "id");
@@ -140,11 +163,15 @@ where
 
             let mut new_props: Vec<PropOrSpread> = vec![];
 
+            // static id used for extraction, `None` if the id is a dynamic expression
+            let mut id: Option<String> = None;
+
             if let Some(id_prop) = id_prop {
                 if let Some(value) = get_expr_as_string(&id_prop.value) {
                     let value = build_prefixed_id(&self.ctx.options, &value, defaults.as_ref())
                         .unwrap_or(value);
-                    new_props.push(create_key_value_prop("id", value.into()));
+                    new_props.push(create_key_value_prop("id", value.clone().into()));
+                    id = Some(value);
                 } else {
                     new_props.push(PropOrSpread::Prop(Box::new(Prop::KeyValue(
                         id_prop.clone(),
@@ -152,40 +179,82 @@ where
                 }
             }
 
+            let resolved_context = context_val.clone().or_else(|| {
+                defaults
+                    .as_ref()
+                    .and_then(|defaults| defaults.context.clone())
+            });
+
+            let mut message: Option<String> = None;
+            let mut placeholders: Vec<String> = vec![];
+            let mut components: Vec<String> = vec![];
+
             if let Some(prop) = message_prop {
                 let mut macro_ctx = MacroCtx::new(self.ctx);
                 let tokens = try_tokenize_expr(&mut macro_ctx, &prop.value).unwrap_or_default();
 
                 let parsed = MessageBuilder::parse(tokens, &self.ctx.options);
+                self.ctx.validate_message(&parsed.message_str, origin);
+
+                if let (Some(id), Some(_)) = (&id, id_prop) {
+                    self.ctx.check_explicit_id(
+                        id,
+                        &parsed.message_str,
+                        resolved_context.as_deref(),
+                        origin,
+                    );
+                }
 
                 if id_prop.is_none() {
-                    let resolved_context = context_val
-                        .as_deref()
-                        .or_else(|| {
-                            defaults
-                                .as_ref()
-                                .and_then(|defaults| defaults.context.as_deref())
-                        })
-                        .unwrap_or_default();
-
-                    new_props.push(create_key_value_prop(
-                        "id",
-                        generate_message_id(
-                            &parsed.message_str,
-                            resolved_context,
-                            self.ctx.options.use_lingui_v5_id_generation,
-                        )
-                        .into(),
-                    ))
+                    let generated = self.ctx.options.generate_message_id(
+                        &parsed.message_str,
+                        resolved_context.as_deref().unwrap_or_default(),
+                    );
+
+                    new_props.push(create_key_value_prop("id", generated.clone().into()));
+                    id = Some(generated);
                 }
 
-                if self.ctx.options.descriptor_fields.should_keep_message() {
+                if let Some(inlined) = self
+                    .ctx
+                    .message_override(id.as_deref(), Some(&parsed.message_str))
+                {
+                    new_props.push(create_key_value_prop("message", inlined));
+                } else if self.ctx.options.descriptor_fields.should_keep_message() {
                     new_props.push(create_key_value_prop("message", parsed.message));
                 }
 
                 if let Some(v) = parsed.values {
                     new_props.push(create_key_value_prop("values", v))
                 }
+
+                message = Some(parsed.message_str);
+                placeholders = parsed.placeholders;
+                components = parsed.component_names;
+            } else if let Some(inlined) = id
+                .as_deref()
+                .and_then(|id| self.ctx.message_override(Some(id), None))
+            {
+                // only an id, the translation can still be inlined
+                new_props.push(create_key_value_prop("message", inlined));
+            }
+
+            if let Some(id) = id {
+                self.ctx.collect_message(ExtractedMessage {
+                    id,
+                    message,
+                    context: resolved_context,
+                    comment: explicit_comment_prop
+                        .and_then(|prop| get_expr_as_string(&prop.value))
+                        .or_else(|| {
+                            defaults
+                                .as_ref()
+                                .and_then(|defaults| defaults.comment.clone())
+                        }),
+                    placeholders,
+                    components,
+                    origin,
+                });
             }
 
             if self.ctx.options.descriptor_fields.should_keep_context() {
@@ -214,6 +283,8 @@ where
                 }
             }
 
+            new_props.extend(self.ctx.origin_prop(origin));
+
             let message_descriptor = Box::new(Expr::Object(ObjectLit {
                 span,
                 props: new_props,
@@ -259,6 +330,7 @@ where
                     return self.create_message_descriptor_from_tokens(
                         tokens,
                         span,
+                        span,
                         defaults.as_ref(),
                     );
                 }
@@ -273,6 +345,7 @@ where
                 let descriptor = self.update_msg_descriptor_props(
                     call.args.clone().into_iter().next().unwrap().expr,
                     call.span(),
+                    call.span(),
                 );
 
                 return *descriptor;
@@ -293,7 +366,7 @@ where
 
                 let msg_dscrpt_expr_span = msg_dscrpt_expr.span();
                 let descriptor =
-                    self.update_msg_descriptor_props(msg_dscrpt_expr, msg_dscrpt_expr_span);
+                    self.update_msg_descriptor_props(msg_dscrpt_expr, msg_dscrpt_expr_span, span);
 
                 return self.create_i18n_fn_call(callee, vec![descriptor.as_arg()], span);
             }
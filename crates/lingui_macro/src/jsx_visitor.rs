@@ -1,11 +1,14 @@
-use crate::ast_utils::get_jsx_attr_value_as_string;
+use crate::ast_utils::{get_jsx_attr_value_as_string, has_words};
+use crate::diagnostics::{Code, Diagnostic};
 use crate::macro_utils::{
-    tokenize_expr_to_arg, tokenize_tpl, try_tokenize_call_expr_as_choice_cmp, MacroCtx,
+    report_invalid_offset, tokenize_expr_to_arg, tokenize_expr_to_token,
+    tokenize_ternary_text_branch, tokenize_tpl, try_tokenize_call_expr_as_choice_cmp,
+    try_tokenize_ternary, MacroCtx, TransformCtx,
 };
 use crate::tokens::{CaseOrOffset, ChoiceCase, MsgArg, MsgToken, TagOpening};
+use swc_core::common::Span;
 use swc_core::ecma::ast::*;
 use swc_core::ecma::atoms::Atom;
-use swc_core::plugin::errors::HANDLER;
 
 pub struct TransJSXVisitor<'a, 'ctx> {
     pub tokens: Vec<MsgToken>,
@@ -37,12 +40,23 @@ impl<'a, 'ctx> TransJSXVisitor<'a, 'ctx> {
     }
 
     pub fn visit_jsx_element(&mut self, el: &JSXElement) {
+        if self.is_trans_element(el) {
+            Diagnostic::new(Code::NestedTrans, el.span, "Nested `<Trans>` is not supported.")
+                .help("Everything inside a JSX macro is already part of its message. Remove the inner `<Trans>` and keep its children, or move it out of the macro to translate it as a separate message.")
+                .emit();
+            return;
+        }
+
         self.visit_jsx_opening_element(&el.opening);
         if !el.opening.self_closing {
             self.visit_jsx_children(&el.children);
-            self.visit_jsx_closing_element();
+            self.visit_jsx_closing_element(el.closing.as_ref().map_or(el.span, |c| c.span));
         }
     }
+
+    fn is_trans_element(&self, el: &JSXElement) -> bool {
+        matches!(&el.opening.name, JSXElementName::Ident(ident) if self.ctx.transform.is_lingui_ident("Trans", ident))
+    }
 }
 
 // taken from babel repo -> packages/babel-types/src/utils/react/cleanJSXElementLiteralChild.ts
@@ -154,10 +168,11 @@ impl TransJSXVisitor<'_, '_> {
                                 value_arg = Some(token_arg)
                             }
                         } else if &ident.sym == "offset" && icu_format != "select" {
-                            if let Some(value) = get_jsx_attr_value_as_string(attr_value) {
-                                cases.push(CaseOrOffset::Offset(value.to_string()))
-                            } else {
-                                // todo: panic offset might be only a number, other forms are not supported
+                            match get_jsx_attr_value_as_string(attr_value) {
+                                Some(value) if value.parse::<i64>().is_ok() => {
+                                    cases.push(CaseOrOffset::Offset(value))
+                                }
+                                _ => report_invalid_offset(attr.span),
                             }
                         } else if let Some(key) = is_allowed_plural_option(&ident.sym) {
                             let mut tokens: Vec<MsgToken> = Vec::new();
@@ -185,21 +200,33 @@ impl TransJSXVisitor<'_, '_> {
                                         // some={<Books />}
                                         Expr::JSXElement(el) => {
                                             let mut visitor = TransJSXVisitor::new(self.ctx);
-                                            visitor.visit_jsx_element(el);
+                                            // some={<Trans>…</Trans>} is the case content itself
+                                            if visitor.is_trans_element(el) {
+                                                visitor.visit_jsx_children(&el.children);
+                                            } else {
+                                                visitor.visit_jsx_element(el);
+                                            }
 
                                             tokens.extend(visitor.tokens);
                                         }
 
                                         _ => {
-                                            let token_arg =
-                                                tokenize_expr_to_arg(self.ctx, exp.clone());
-                                            tokens.push(MsgToken::Arg(token_arg));
+                                            tokens.push(tokenize_expr_to_token(
+                                                self.ctx,
+                                                exp.clone(),
+                                            ));
                                         }
                                     }
                                 }
 
                                 _ => {
-                                    // todo unsupported syntax
+                                    Diagnostic::new(
+                                        Code::UnsupportedCaseValue,
+                                        attr.span,
+                                        format!("Unsupported value for the `{key}` case."),
+                                    )
+                                    .help(format!("Use a string, e.g. `{key}=\"# books\"`, or an expression in braces, e.g. `{key}={{<b># books</b>}}`."))
+                                    .emit();
                                 }
                             }
 
@@ -208,11 +235,9 @@ impl TransJSXVisitor<'_, '_> {
                     }
                 }
             } else {
-                HANDLER.with(|h| {
-                    h.struct_span_warn(el.span, "Unsupported Syntax")
-                        .note("The spread expression could not be analyzed at compile time. Consider to use static values.")
-                        .emit()
-                });
+                Diagnostic::new(Code::UnsupportedSpread, el.span, "Unsupported Syntax")
+                    .help("The spread expression could not be analyzed at compile time. Consider to use static values.")
+                    .emit();
             }
         }
 
@@ -224,11 +249,9 @@ impl TransJSXVisitor<'_, '_> {
                 cases: Some(cases),
             }));
         } else {
-            HANDLER.with(|h| {
-                h.struct_span_warn(el.span, "Incorrect Macro Usage")
-                    .note("The macro element should have a `value` property")
-                    .emit()
-            });
+            Diagnostic::new(Code::MissingChoiceValue, el.span, "Incorrect Macro Usage")
+                .help("The macro element should have a `value` property")
+                .emit();
         }
     }
 }
@@ -236,10 +259,6 @@ impl TransJSXVisitor<'_, '_> {
 impl TransJSXVisitor<'_, '_> {
     pub fn visit_jsx_opening_element(&mut self, el: &JSXOpeningElement) {
         if let JSXElementName::Ident(ident) = &el.name {
-            if self.ctx.transform.is_lingui_ident("Trans", ident) {
-                return;
-            }
-
             if self.ctx.transform.is_lingui_jsx_choice_cmp(ident) {
                 let icu_method = self
                     .ctx
@@ -264,8 +283,8 @@ impl TransJSXVisitor<'_, '_> {
         }));
     }
 
-    fn visit_jsx_closing_element(&mut self) {
-        self.tokens.push(MsgToken::TagClosing);
+    fn visit_jsx_closing_element(&mut self, span: Span) {
+        self.tokens.push(MsgToken::TagClosing(span));
     }
 
     fn visit_jsx_text(&mut self, el: &JSXText) {
@@ -286,6 +305,7 @@ impl TransJSXVisitor<'_, '_> {
                     if let Some(tokens) = try_tokenize_call_expr_as_choice_cmp(self.ctx, call) {
                         self.tokens.extend(tokens);
                     } else {
+                        self.warn_leaked_text(exp);
                         let arg = tokenize_expr_to_arg(self.ctx, exp.clone());
                         self.tokens.push(MsgToken::Arg(arg));
                     }
@@ -298,11 +318,162 @@ impl TransJSXVisitor<'_, '_> {
                 Expr::Tpl(tpl) => {
                     self.tokens.extend(tokenize_tpl(self.ctx, tpl));
                 }
+
+                // {isAdmin ? "administrator" : <b>member</b>}
+                Expr::Cond(cond) => {
+                    let token = try_tokenize_ternary(self.ctx, cond, tokenize_ternary_jsx_branch)
+                        .unwrap_or_else(|| {
+                            self.warn_leaked_text(exp);
+                            tokenize_expr_to_token(self.ctx, exp.clone())
+                        });
+                    self.tokens.push(token);
+                }
+
                 _ => {
-                    let arg = tokenize_expr_to_arg(self.ctx, exp.clone());
-                    self.tokens.push(MsgToken::Arg(arg));
+                    let token = tokenize_expr_to_token(self.ctx, exp.clone());
+                    if matches!(token, MsgToken::Arg(_)) {
+                        self.warn_leaked_text(exp);
+                    }
+                    self.tokens.push(token);
+                }
+            }
+        }
+    }
+
+    /// Warn about text rendered by an expression which becomes a placeholder
+    /// value, e.g. `{flag && <b>Important</b>}`. The value is passed to the
+    /// message as is, so its text is never extracted.
+    fn warn_leaked_text(&self, expr: &Expr) {
+        let mut spans = Vec::new();
+        collect_leaked_text(self.ctx.transform, expr, &mut spans);
+
+        for span in spans {
+            Diagnostic::new(
+                Code::UntranslatedPlaceholderText,
+                span,
+                "Text inside this placeholder is not part of the message and will not be translated.",
+            )
+            .help("The expression is passed to the message as a value. Move the text into the message, or wrap it in its own `<Trans>`.")
+            .emit();
+        }
+    }
+}
+
+/// Spans of text which `expr` renders. Only the parts which end up in the
+/// output are followed: operands of `&&`, `||` and `??`, ternary branches,
+/// JSX children, arrays and the bodies of callbacks like `items.map(…)`.
+/// String branches of a ternary are left to [`try_tokenize_ternary`] and
+/// nested macros are translated on their own.
+fn collect_leaked_text(ctx: &TransformCtx, expr: &Expr, spans: &mut Vec<Span>) {
+    match expr {
+        Expr::Lit(Lit::Str(str)) if has_words(&str.value.to_string_lossy()) => spans.push(str.span),
+        Expr::Tpl(tpl) if tpl.quasis.iter().any(|quasi| has_words(&quasi.raw)) => {
+            spans.push(tpl.span)
+        }
+        Expr::Paren(ParenExpr { expr, .. }) => collect_leaked_text(ctx, expr, spans),
+        Expr::Bin(BinExpr {
+            op, left, right, ..
+        }) => match op {
+            BinaryOp::LogicalAnd => collect_leaked_text(ctx, right, spans),
+            BinaryOp::LogicalOr | BinaryOp::NullishCoalescing => {
+                collect_leaked_text(ctx, left, spans);
+                collect_leaked_text(ctx, right, spans);
+            }
+            _ => {}
+        },
+        Expr::Cond(cond) => {
+            for branch in [&cond.cons, &cond.alt] {
+                if !matches!(branch.as_ref(), Expr::Lit(Lit::Str(_)) | Expr::Tpl(_)) {
+                    collect_leaked_text(ctx, branch, spans);
                 }
             }
         }
+        Expr::Array(array) => {
+            for elem in array.elems.iter().flatten() {
+                collect_leaked_text(ctx, &elem.expr, spans);
+            }
+        }
+        Expr::Call(call) => {
+            for arg in &call.args {
+                if matches!(arg.expr.as_ref(), Expr::Arrow(_) | Expr::Fn(_)) {
+                    collect_leaked_text(ctx, &arg.expr, spans);
+                }
+            }
+        }
+        Expr::Arrow(arrow) => match arrow.body.as_ref() {
+            BlockStmtOrExpr::Expr(body) => collect_leaked_text(ctx, body, spans),
+            BlockStmtOrExpr::BlockStmt(block) => collect_returned_text(ctx, block, spans),
+        },
+        Expr::Fn(FnExpr { function, .. }) => {
+            if let Some(body) = &function.body {
+                collect_returned_text(ctx, body, spans);
+            }
+        }
+        Expr::JSXElement(el) => collect_leaked_element_text(ctx, el, spans),
+        Expr::JSXFragment(frag) => collect_leaked_jsx_text(ctx, &frag.children, spans),
+        _ => {}
+    }
+}
+
+fn collect_leaked_element_text(ctx: &TransformCtx, el: &JSXElement, spans: &mut Vec<Span>) {
+    if let JSXElementName::Ident(ident) = &el.opening.name {
+        if ctx.is_lingui_ident("Trans", ident) || ctx.is_lingui_jsx_choice_cmp(ident) {
+            return;
+        }
+    }
+    collect_leaked_jsx_text(ctx, &el.children, spans);
+}
+
+fn collect_leaked_jsx_text(
+    ctx: &TransformCtx,
+    children: &[JSXElementChild],
+    spans: &mut Vec<Span>,
+) {
+    for child in children {
+        match child {
+            JSXElementChild::JSXText(text) => {
+                if has_words(&text.value) {
+                    spans.push(text.span);
+                }
+            }
+            JSXElementChild::JSXExprContainer(JSXExprContainer {
+                expr: JSXExpr::Expr(expr),
+                ..
+            }) => collect_leaked_text(ctx, expr, spans),
+            JSXElementChild::JSXElement(el) => collect_leaked_element_text(ctx, el, spans),
+            JSXElementChild::JSXFragment(frag) => {
+                collect_leaked_jsx_text(ctx, &frag.children, spans)
+            }
+            _ => {}
+        }
+    }
+}
+
+fn collect_returned_text(ctx: &TransformCtx, block: &BlockStmt, spans: &mut Vec<Span>) {
+    for stmt in &block.stmts {
+        if let Stmt::Return(ReturnStmt { arg: Some(arg), .. }) = stmt {
+            collect_leaked_text(ctx, arg, spans);
+        }
+    }
+}
+
+/// Branch tokenizer for ternaries inside JSX, which also accepts elements
+fn tokenize_ternary_jsx_branch(ctx: &mut MacroCtx, expr: &Expr) -> Option<Vec<MsgToken>> {
+    match expr {
+        Expr::Paren(ParenExpr { expr, .. }) => tokenize_ternary_jsx_branch(ctx, expr),
+        Expr::Cond(cond) => {
+            try_tokenize_ternary(ctx, cond, tokenize_ternary_jsx_branch).map(|token| vec![token])
+        }
+        Expr::JSXElement(el) => {
+            let mut visitor = TransJSXVisitor::new(ctx);
+            visitor.visit_jsx_element(el);
+            Some(visitor.tokens)
+        }
+        Expr::JSXFragment(frag) => {
+            let mut visitor = TransJSXVisitor::new(ctx);
+            visitor.visit_jsx_children(&frag.children);
+            Some(visitor.tokens)
+        }
+        _ => tokenize_ternary_text_branch(ctx, expr),
     }
 }
@@ -4,12 +4,11 @@ use swc_core::common::{SourceMapper, Span, Spanned, SyntaxContext, DUMMY_SP};
 
 use swc_core::common::comments::*;
 use swc_core::ecma::utils::private_ident;
-use swc_core::plugin::errors::HANDLER;
 use swc_core::{
     ecma::{
         ast::*,
         utils::quote_ident,
-        visit::{Fold, FoldWith},
+        visit::{Fold, FoldWith, VisitWith},
     },
     plugin::{
         metadata::TransformPluginMetadataContextKind, plugin_transform,
@@ -20,20 +19,28 @@ use swc_core::{
 mod ast_utils;
 mod builder;
 mod comment_directive;
+mod custom_macro;
+pub mod diagnostics;
+mod extract;
 mod generate_id;
+pub mod icu;
 mod js_macro_folder;
 mod jsx_visitor;
+mod lint;
 mod macro_utils;
 mod options;
+mod plural_rules;
 mod tokens;
 
-use crate::generate_id::*;
 use crate::macro_utils::*;
 use ast_utils::*;
 use builder::*;
 use comment_directive::LinguiCommentDirectives;
+use custom_macro::CustomMacroExpander;
+use diagnostics::{Code, Diagnostic};
 use js_macro_folder::JsMacroFolder;
 use jsx_visitor::TransJSXVisitor;
+use lint::HardcodedStringLinter;
 
 pub struct IdentReplacer {
     from: Id,
@@ -57,6 +64,8 @@ where
     C: Comments + Clone,
 {
     has_lingui_macro_imports: bool,
+    /// macros were loaded with `require()`, so runtime modules are required too
+    uses_require: bool,
     ctx: TransformCtx,
     comments: Option<C>,
     source_map: Lrc<dyn SourceMapper>,
@@ -71,14 +80,241 @@ where
         comments: Option<C>,
         source_map: Lrc<dyn SourceMapper>,
     ) -> LinguiMacroFolder<C> {
+        let mut ctx = TransformCtx::new(options);
+        ctx.source_map = Some(source_map.clone());
+
         LinguiMacroFolder {
             has_lingui_macro_imports: false,
-            ctx: TransformCtx::new(options),
+            uses_require: false,
+            ctx,
             comments,
             source_map,
         }
     }
 
+    /// Name of the transformed file, reported in the `origin` of descriptors
+    pub fn with_filename(mut self, filename: String) -> LinguiMacroFolder<C> {
+        self.ctx.filename = Some(filename);
+        self
+    }
+
+    /// Enable extraction mode: every message descriptor produced during the fold
+    /// is recorded into the given collector.
+    pub fn with_message_collector(mut self, collector: MessageCollector) -> LinguiMacroFolder<C> {
+        self.ctx.message_collector = Some(collector);
+        self
+    }
+
+    fn transform_module(&mut self, mut node: Module) -> Module {
+        self.has_lingui_macro_imports = node.body.iter().any(|m| match m {
+            ModuleItem::ModuleDecl(ModuleDecl::Import(imp)) => {
+                self.ctx
+                    .options
+                    .macro_packages
+                    .contains(&imp.src.value.to_string_lossy())
+                    || self.ctx.has_custom_macro_imports(imp)
+            }
+            ModuleItem::Stmt(stmt) => self.has_macro_require(stmt),
+            _ => false,
+        });
+
+        if !self.has_lingui_macro_imports {
+            return node;
+        }
+
+        self.ensure_source_directives(node.span);
+
+        let mut insert_index: usize = 0;
+        let mut index = 0;
+
+        node.body.retain_mut(|m| {
+            match m {
+                ModuleItem::ModuleDecl(ModuleDecl::Import(imp)) => {
+                    // drop macro imports
+                    if self
+                        .ctx
+                        .options
+                        .macro_packages
+                        .contains(&imp.src.value.to_string_lossy())
+                    {
+                        self.ctx.register_macro_import(imp);
+                        insert_index = index;
+                        return false;
+                    }
+
+                    if self.ctx.has_custom_macro_imports(imp) {
+                        insert_index = index;
+                        if !self.ctx.take_custom_macro_imports(imp) {
+                            return false;
+                        }
+                    }
+                }
+                ModuleItem::Stmt(stmt) if self.has_macro_require(stmt) => {
+                    self.uses_require = true;
+                    insert_index = index;
+                    if !self.take_macro_requires(stmt) {
+                        return false;
+                    }
+                }
+                _ => {}
+            }
+
+            index += 1;
+            true
+        });
+
+        self.ctx.register_string_constants(&node);
+
+        if self.ctx.has_macro_namespaces() {
+            node = node.fold_children_with(&mut NamespaceMacroResolver { ctx: &mut self.ctx });
+        }
+
+        if self.ctx.has_custom_macros() {
+            node = node.fold_children_with(&mut CustomMacroExpander { ctx: &mut self.ctx });
+        }
+
+        if self.ctx.options.lint_hardcoded_strings {
+            node.visit_children_with(&mut HardcodedStringLinter { ctx: &self.ctx });
+        }
+
+        node = node.fold_children_with(self);
+
+        for (source, export, local) in self.runtime_imports() {
+            let item = if self.uses_require {
+                ModuleItem::Stmt(create_require(source.into(), export, local))
+            } else {
+                create_import(source.into(), export, local)
+            };
+
+            node.body.insert(insert_index, item);
+        }
+
+        node
+    }
+
+    fn transform_script(&mut self, mut node: Script) -> Script {
+        self.has_lingui_macro_imports = node.body.iter().any(|stmt| self.has_macro_require(stmt));
+
+        if !self.has_lingui_macro_imports {
+            return node;
+        }
+
+        self.ensure_source_directives(node.span);
+        self.uses_require = true;
+
+        let mut insert_index: usize = 0;
+        let mut index = 0;
+
+        node.body.retain_mut(|stmt| {
+            if self.has_macro_require(stmt) {
+                insert_index = index;
+                if !self.take_macro_requires(stmt) {
+                    return false;
+                }
+            }
+
+            index += 1;
+            true
+        });
+
+        self.ctx.register_string_constants(&node);
+
+        if self.ctx.has_macro_namespaces() {
+            node = node.fold_children_with(&mut NamespaceMacroResolver { ctx: &mut self.ctx });
+        }
+
+        if self.ctx.has_custom_macros() {
+            node = node.fold_children_with(&mut CustomMacroExpander { ctx: &mut self.ctx });
+        }
+
+        if self.ctx.options.lint_hardcoded_strings {
+            node.visit_children_with(&mut HardcodedStringLinter { ctx: &self.ctx });
+        }
+
+        node = node.fold_children_with(self);
+
+        for (source, export, local) in self.runtime_imports() {
+            node.body
+                .insert(insert_index, create_require(source.into(), export, local));
+        }
+
+        node
+    }
+
+    fn has_macro_require(&self, stmt: &Stmt) -> bool {
+        let Stmt::Decl(Decl::Var(var)) = stmt else {
+            return false;
+        };
+
+        var.decls.iter().any(|decl| {
+            get_require_source(decl.init.as_deref()).is_some_and(|source| {
+                self.ctx.options.macro_packages.contains(&source)
+                    || self.ctx.has_custom_macro_require(&source, &decl.name)
+            })
+        })
+    }
+
+    /// Register and drop `const { t } = require("@lingui/core/macro")` declarators
+    /// and the custom macros of `const { tc } = require("@acme/i18n")`.
+    /// Returns false when nothing is left of the statement.
+    fn take_macro_requires(&mut self, stmt: &mut Stmt) -> bool {
+        let Stmt::Decl(Decl::Var(var)) = stmt else {
+            return true;
+        };
+
+        let decls = std::mem::take(&mut var.decls);
+        for mut decl in decls {
+            match get_require_source(decl.init.as_deref()) {
+                Some(source) if self.ctx.options.macro_packages.contains(&source) => {
+                    self.ctx.register_macro_require(&decl.name);
+                }
+                Some(source) if self.ctx.has_custom_macro_require(&source, &decl.name) => {
+                    if self.ctx.take_custom_macro_require(&source, &mut decl.name) {
+                        var.decls.push(decl);
+                    }
+                }
+                _ => var.decls.push(decl),
+            }
+        }
+
+        !var.decls.is_empty()
+    }
+
+    /// Runtime modules used by the transformed macros as `(source, export, local)`,
+    /// each inserted before the previous one.
+    fn runtime_imports(&self) -> Vec<(String, IdentName, IdentName)> {
+        let mut imports = vec![];
+
+        if self.ctx.should_add_18n_import {
+            let (source, export) = self.ctx.options.runtime_modules.i18n.clone();
+            imports.push((
+                source,
+                quote_ident!(export[..]),
+                self.ctx.runtime_idents.i18n.clone().into(),
+            ));
+        }
+
+        if self.ctx.should_add_trans_import {
+            let (source, export) = self.ctx.options.runtime_modules.trans.clone();
+            imports.push((
+                source,
+                quote_ident!(export[..]),
+                self.ctx.runtime_idents.trans.clone(),
+            ));
+        }
+
+        if self.ctx.should_add_uselingui_import {
+            let (source, export) = self.ctx.options.runtime_modules.use_lingui.clone();
+            imports.push((
+                source,
+                quote_ident!(export[..]),
+                self.ctx.runtime_idents.use_lingui.clone(),
+            ));
+        }
+
+        imports
+    }
+
     fn ensure_source_directives(&mut self, module_span: Span) {
         if !self.ctx.directives.is_empty() {
             return;
@@ -146,6 +382,7 @@ where
         }
 
         let parsed = MessageBuilder::parse(trans_visitor.tokens, &self.ctx.options);
+        self.ctx.validate_message(&parsed.message_str, el.span);
         let id_attr = get_jsx_attr(&el.opening, "id").and_then(|attr| attr.value.as_ref());
         let defaults = self.ctx.get_comment_directive(el.span.lo).cloned();
 
@@ -160,22 +397,31 @@ where
 
         let mut message_descriptor_props: Vec<PropOrSpread> = vec![];
 
-        if let Some(id_value) = id_attr.and_then(get_jsx_attr_value_as_string) {
-            let id_value = build_prefixed_id(&self.ctx.options, &id_value, defaults.as_ref())
+        let id = if let Some(id_value) = id_attr.and_then(get_jsx_attr_value_as_string) {
+            let id = build_prefixed_id(&self.ctx.options, &id_value, defaults.as_ref())
                 .unwrap_or(id_value);
-
-            message_descriptor_props.push(create_key_value_prop("id", id_value.into()));
-        } else {
-            message_descriptor_props.push(create_key_value_prop(
-                "id",
-                generate_message_id(
+            // `<Trans id="save" />` only references a message defined elsewhere
+            if !parsed.message_str.is_empty() {
+                self.ctx.check_explicit_id(
+                    &id,
                     &parsed.message_str,
-                    resolved_context.as_deref().unwrap_or_default(),
-                    self.ctx.options.use_lingui_v5_id_generation,
-                )
-                .into(),
-            ));
-        }
+                    resolved_context.as_deref(),
+                    el.span,
+                );
+            }
+            id
+        } else {
+            self.ctx.options.generate_message_id(
+                &parsed.message_str,
+                resolved_context.as_deref().unwrap_or_default(),
+            )
+        };
+
+        let inlined = self
+            .ctx
+            .message_override(Some(&id), Some(&parsed.message_str));
+
+        message_descriptor_props.push(create_key_value_prop("id", id.clone().into()));
 
         if let Some(exp) = parsed.values {
             message_descriptor_props.push(create_key_value_prop("values", exp));
@@ -185,21 +431,34 @@ where
             message_descriptor_props.push(create_key_value_prop("components", exp));
         }
 
-        if self.ctx.options.descriptor_fields.should_keep_comment() {
-            let comment_attr_val = get_jsx_attr(&el.opening, "comment")
-                .and_then(|attr| get_jsx_attr_value_as_string(attr.value.as_ref()?));
-
-            if let Some(comment_attr_val) = comment_attr_val.or_else(|| {
+        let comment = get_jsx_attr(&el.opening, "comment")
+            .and_then(|attr| get_jsx_attr_value_as_string(attr.value.as_ref()?))
+            .or_else(|| {
                 defaults
                     .as_ref()
                     .and_then(|defaults| defaults.comment.clone())
-            }) {
-                message_descriptor_props
-                    .push(create_key_value_prop("comment", comment_attr_val.into()));
+            });
+
+        self.ctx.collect_message(ExtractedMessage {
+            id,
+            // `<Trans id="save" />` has no message, same as `t({ id })`
+            message: Some(parsed.message_str.clone()).filter(|message| !message.is_empty()),
+            context: resolved_context.clone(),
+            comment: comment.clone(),
+            placeholders: parsed.placeholders,
+            components: parsed.component_names,
+            origin: el.span,
+        });
+
+        if self.ctx.options.descriptor_fields.should_keep_comment() {
+            if let Some(comment) = comment {
+                message_descriptor_props.push(create_key_value_prop("comment", comment.into()));
             }
         }
 
-        if self.ctx.options.descriptor_fields.should_keep_message() {
+        if let Some(inlined) = inlined {
+            message_descriptor_props.push(create_key_value_prop("message", inlined));
+        } else if self.ctx.options.descriptor_fields.should_keep_message() {
             message_descriptor_props.push(create_key_value_prop("message", parsed.message));
         }
 
@@ -218,6 +477,8 @@ where
             }
         }
 
+        message_descriptor_props.extend(self.ctx.origin_prop(el.span));
+
         let message_descriptor = Expr::Object(ObjectLit {
             span: message_dscrptr_span,
             props: message_descriptor_props,
@@ -351,15 +612,17 @@ where
                                                 }),
                                             };
                                         } else {
-                                            HANDLER.with(|h| {
-                                                h.struct_span_warn(decl.span, "Unsupported Syntax")
-                                                        .note(
+                                            Diagnostic::new(
+                                                Code::UnsupportedUseLingui,
+                                                decl.span,
+                                                "Unsupported Syntax",
+                                            )
+                                            .help(
 r#"You have to destructure `t` when using the `useLingui` macro, i.e:
  const { t } = useLingui()
  or
  const { t: _ } = useLingui()"#)
-                                                        .emit()
-                                            });
+                                            .emit();
                                         }
                                     }
                                 }
@@ -399,90 +662,60 @@ r#"You have to destructure `t` when using the `useLingui` macro, i.e:
     }
 }
 
-impl<C> Fold for LinguiMacroFolder<C>
-where
-    C: Comments + Clone,
-{
-    fn fold_module(&mut self, mut node: Module) -> Module {
-        self.has_lingui_macro_imports = node.body.iter().any(|m| {
-            matches!(m,
-                ModuleItem::ModuleDecl(ModuleDecl::Import(imp))
-                if self.ctx.options.macro_packages.contains(&imp.src.value.to_string_lossy())
-            )
-        });
-
-        if !self.has_lingui_macro_imports {
-            return node;
-        }
-
-        self.ensure_source_directives(node.span);
-
-        let mut insert_index: usize = 0;
-        let mut index = 0;
+/// Rewrites members of namespace imports of macro packages, `m.t` or
+/// `<m.Trans>`, into plain identifiers which the macros recognize.
+struct NamespaceMacroResolver<'a> {
+    ctx: &'a mut TransformCtx,
+}
 
-        node.body.retain(|m| {
-            if let ModuleItem::ModuleDecl(ModuleDecl::Import(imp)) = m {
-                // drop macro imports
-                if self
-                    .ctx
-                    .options
-                    .macro_packages
-                    .contains(&imp.src.value.to_string_lossy())
-                {
-                    self.ctx.register_macro_import(imp);
-                    insert_index = index;
-                    return false;
+impl Fold for NamespaceMacroResolver<'_> {
+    fn fold_expr(&mut self, expr: Expr) -> Expr {
+        if let Expr::Member(MemberExpr {
+            span,
+            obj,
+            prop: MemberProp::Ident(name),
+        }) = &expr
+        {
+            if let Expr::Ident(namespace) = &**obj {
+                if let Some(ident) = self.ctx.resolve_namespace_member(namespace, name, *span) {
+                    return Expr::Ident(ident);
                 }
             }
-
-            index += 1;
-            true
-        });
-
-        node = node.fold_children_with(self);
-
-        if self.ctx.should_add_18n_import {
-            let (i18n_source, i18n_export) = self.ctx.options.runtime_modules.i18n.clone();
-
-            node.body.insert(
-                insert_index,
-                create_import(
-                    i18n_source.into(),
-                    quote_ident!(i18n_export[..]),
-                    self.ctx.runtime_idents.i18n.clone().into(),
-                ),
-            );
         }
 
-        if self.ctx.should_add_trans_import {
-            let (trans_source, trans_export) = self.ctx.options.runtime_modules.trans.clone();
+        expr.fold_children_with(self)
+    }
 
-            node.body.insert(
-                insert_index,
-                create_import(
-                    trans_source.into(),
-                    quote_ident!(trans_export[..]),
-                    self.ctx.runtime_idents.trans.clone(),
-                ),
-            );
+    fn fold_jsx_element_name(&mut self, name: JSXElementName) -> JSXElementName {
+        if let JSXElementName::JSXMemberExpr(JSXMemberExpr {
+            span,
+            obj: JSXObject::Ident(namespace),
+            prop,
+        }) = &name
+        {
+            if let Some(ident) = self.ctx.resolve_namespace_member(namespace, prop, *span) {
+                return JSXElementName::Ident(ident);
+            }
         }
 
-        if self.ctx.should_add_uselingui_import {
-            let (use_lingui_source, use_lingui_export) =
-                self.ctx.options.runtime_modules.use_lingui.clone();
+        name
+    }
+}
 
-            node.body.insert(
-                insert_index,
-                create_import(
-                    use_lingui_source.into(),
-                    quote_ident!(use_lingui_export[..]),
-                    self.ctx.runtime_idents.use_lingui.clone(),
-                ),
-            );
-        }
+impl<C> Fold for LinguiMacroFolder<C>
+where
+    C: Comments + Clone,
+{
+    fn fold_module(&mut self, node: Module) -> Module {
+        let config = self.ctx.diagnostics_config();
+        diagnostics::with_config(config, || self.transform_module(node))
+    }
 
-        node
+    fn fold_script(&mut self, node: Script) -> Script {
+        let config = self.ctx.diagnostics_config();
+        diagnostics::with_config(config, || self.transform_script(node))
     }
+
     fn fold_arrow_expr(&mut self, n: ArrowExpr) -> ArrowExpr {
         // If no package that we care about is imported, skip the following
         // transformation logic.
@@ -563,12 +796,13 @@ where
         el = el.fold_with(&mut JsMacroFolder::new(&mut self.ctx, &self.comments));
 
         if let JSXElementName::Ident(ident) = &el.opening.name {
+            // placeholder values may hold macros of their own, e.g. {flag && <Trans>…</Trans>}
             if self.ctx.is_lingui_ident("Trans", ident) {
-                return self.transform_jsx_macro(el, true);
+                return self.transform_jsx_macro(el, true).fold_children_with(self);
             }
 
             if self.ctx.is_lingui_jsx_choice_cmp(ident) {
-                return self.transform_jsx_macro(el, false);
+                return self.transform_jsx_macro(el, false).fold_children_with(self);
             }
         }
 
@@ -576,9 +810,11 @@ where
     }
 }
 
+pub use self::extract::{ExtractedMessage, MessageCollector};
+pub use self::generate_id::{generate_message_id, HashAlgorithm, IdGeneration, IdStrategy};
 pub use self::options::{
-    DescriptorFields, LinguiJsOptions, LinguiOptions, MacroPackagesConfig, RuntimeModulesConfigMap,
-    RuntimeModulesConfigMapNormalized,
+    CustomMacro, CustomMacroArg, CustomMacroKind, DescriptorFields, LinguiJsOptions, LinguiOptions,
+    MacroPackagesConfig, MessageOutput, RuntimeModulesConfigMap, RuntimeModulesConfigMapNormalized,
 };
 
 #[plugin_transform]
@@ -596,11 +832,30 @@ pub fn process_transform(program: Program, metadata: TransformPluginProgramMetad
             .unwrap_or_default(),
     );
 
+    let filename = metadata
+        .get_context(&TransformPluginMetadataContextKind::Filename)
+        .map(|filename| {
+            // prefer paths relative to the project, like the Lingui extractor
+            metadata
+                .get_context(&TransformPluginMetadataContextKind::Cwd)
+                .and_then(|cwd| {
+                    std::path::Path::new(&filename)
+                        .strip_prefix(cwd)
+                        .ok()
+                        .map(|path| path.to_string_lossy().replace('\\', "/"))
+                })
+                .unwrap_or(filename)
+        });
+
     let mut folder = LinguiMacroFolder::new(
         config,
         metadata.comments,
         Lrc::new(metadata.source_map) as Lrc<dyn SourceMapper>,
     );
 
+    if let Some(filename) = filename {
+        folder = folder.with_filename(filename);
+    }
+
     program.fold_with(&mut folder)
 }
@@ -0,0 +1,77 @@
+//! The `lintHardcodedStrings` check.
+//!
+//! Reports user-facing text which is not part of any message: JSX text
+//! children and the `alt`, `title`, `placeholder` and `aria-label` attributes
+//! written as plain strings. It runs after namespace imports and custom macros
+//! are resolved, so it recognizes the macros the same way the transform does.
+
+use crate::ast_utils::{get_jsx_attr_value_as_string, has_words};
+use crate::diagnostics::{Code, Diagnostic};
+use crate::macro_utils::TransformCtx;
+use swc_core::common::{BytePos, Span};
+use swc_core::ecma::ast::*;
+use swc_core::ecma::visit::{Visit, VisitWith};
+
+const USER_FACING_ATTRS: [&str; 4] = ["alt", "title", "placeholder", "aria-label"];
+
+pub struct HardcodedStringLinter<'a> {
+    pub ctx: &'a TransformCtx,
+}
+
+fn report(span: Span, message: String, help: String) {
+    Diagnostic::new(Code::HardcodedString, span, message)
+        .help(help)
+        .emit();
+}
+
+impl Visit for HardcodedStringLinter<'_> {
+    fn visit_jsx_element(&mut self, el: &JSXElement) {
+        // everything inside a JSX macro is either the message or a placeholder value
+        if let JSXElementName::Ident(ident) = &el.opening.name {
+            if self.ctx.is_lingui_ident("Trans", ident) || self.ctx.is_lingui_jsx_choice_cmp(ident)
+            {
+                return;
+            }
+        }
+
+        el.visit_children_with(self);
+    }
+
+    fn visit_jsx_attr(&mut self, attr: &JSXAttr) {
+        if let (JSXAttrName::Ident(name), Some(value)) = (&attr.name, &attr.value) {
+            if USER_FACING_ATTRS.contains(&name.sym.as_ref())
+                && get_jsx_attr_value_as_string(value).is_some_and(|text| has_words(&text))
+            {
+                report(
+                    attr.span,
+                    format!("Hardcoded `{}` attribute is not translated.", name.sym),
+                    format!(
+                        "Use a translated string instead, e.g. `{}={{t`…`}}` with `t` from `useLingui()`.",
+                        name.sym
+                    ),
+                );
+            }
+        }
+
+        attr.visit_children_with(self);
+    }
+
+    fn visit_jsx_text(&mut self, text: &JSXText) {
+        if has_words(&text.value) {
+            // point at the text itself, without the indentation around it
+            let raw: &str = &text.raw;
+            let leading = raw.len() - raw.trim_start().len();
+            let trailing = raw.len() - raw.trim_end().len();
+            let span = Span::new(
+                text.span.lo + BytePos(leading as u32),
+                text.span.hi - BytePos(trailing as u32),
+            );
+
+            report(
+                span,
+                "Hardcoded text is not translated.".into(),
+                "Wrap it in `<Trans>` so it is extracted for translation.".into(),
+            );
+        }
+    }
+}
@@ -1,12 +1,128 @@
 use crate::ast_utils::*;
 use crate::comment_directive::{DirectiveValues, LinguiCommentDirectives};
+use crate::diagnostics::{Code, Diagnostic, DiagnosticsConfig};
+use crate::extract::{ExtractedMessage, MessageCollector};
+use crate::icu;
+use crate::options::{CustomMacro, MessageOutput};
+use crate::plural_rules;
 use crate::tokens::*;
 use crate::LinguiOptions;
+use std::cell::RefCell;
 use std::collections::{HashMap, HashSet};
-use swc_core::common::{BytePos, Spanned};
+use std::rc::Rc;
+use swc_core::common::sync::Lrc;
+use swc_core::common::{BytePos, Mark, SourceMapper, Span, Spanned, SyntaxContext, DUMMY_SP};
 use swc_core::ecma::utils::quote_ident;
+use swc_core::ecma::visit::{Visit, VisitWith};
 use swc_core::ecma::{ast::*, atoms::Atom};
-use swc_core::plugin::errors::HANDLER;
+
+/// Warn about choices without an `other` case and, when the source locale is
+/// known, about plural cases the locale never selects, e.g. `few` in English.
+/// Exact matches like `=0` are always allowed.
+fn check_plural_categories(tokens: &[icu::Token], locale: Option<&str>, span: Span) {
+    for token in tokens {
+        let icu::Token::Select {
+            arg, kind, cases, ..
+        } = token
+        else {
+            continue;
+        };
+
+        if !cases.iter().any(|case| case.key == "other") {
+            Diagnostic::new(
+                Code::MissingOtherCase,
+                span,
+                format!("Missing `other` case in `{arg}` {}.", kind.as_str()),
+            )
+            .help("`other` is used for every value no other case matches, without it those values render nothing.")
+            .emit();
+        }
+
+        let categories = locale.and_then(|locale| match kind {
+            icu::SelectKind::Plural => Some((
+                locale,
+                plural_rules::cardinal_categories(locale)?,
+                "cardinal",
+            )),
+            icu::SelectKind::SelectOrdinal => {
+                Some((locale, plural_rules::ordinal_categories(locale)?, "ordinal"))
+            }
+            icu::SelectKind::Select => None,
+        });
+
+        for case in cases {
+            if let Some((locale, categories, kind_name)) = categories {
+                if !case.key.starts_with('=') && !categories.contains(&case.key.as_str()) {
+                    Diagnostic::new(
+                        Code::UnusedPluralCategory,
+                        span,
+                        format!(
+                            "Plural category `{}` is not used by the `{locale}` locale.",
+                            case.key
+                        ),
+                    )
+                    .help(format!(
+                        "`{locale}` uses the {kind_name} categories: {}.",
+                        categories.join(", ")
+                    ))
+                    .emit();
+                }
+            }
+
+            check_plural_categories(&case.tokens, locale, span);
+        }
+    }
+}
+
+/// Text of a compile-time string: a string literal, a known constant, a `+`
+/// concatenation or a template literal made only of those
+fn eval_string(constants: &HashMap<Id, String>, expr: &Expr) -> Option<String> {
+    match unwrap_ts_only_expr(expr) {
+        Expr::Lit(Lit::Str(str)) => Some(str.value.to_string_lossy().into_owned()),
+        Expr::Tpl(tpl) => {
+            let mut text = String::new();
+            for (i, quasi) in tpl.quasis.iter().enumerate() {
+                text.push_str(&quasi.cooked.as_ref()?.to_string_lossy());
+                if let Some(expr) = tpl.exprs.get(i) {
+                    text.push_str(&eval_string(constants, expr)?);
+                }
+            }
+            Some(text)
+        }
+        Expr::Ident(ident) => constants.get(&ident.to_id()).cloned(),
+        Expr::Paren(ParenExpr { expr, .. })
+        | Expr::TsConstAssertion(TsConstAssertion { expr, .. }) => eval_string(constants, expr),
+        Expr::Bin(BinExpr {
+            op: BinaryOp::Add,
+            left,
+            right,
+            ..
+        }) => Some(eval_string(constants, left)? + eval_string(constants, right)?.as_str()),
+        _ => None,
+    }
+}
+
+/// Collects `const` bindings initialized with a compile-time string, in source
+/// order so constants can be built from earlier ones
+pub struct StringConstantCollector<'a> {
+    constants: &'a mut HashMap<Id, String>,
+}
+
+impl Visit for StringConstantCollector<'_> {
+    fn visit_var_decl(&mut self, decl: &VarDecl) {
+        if decl.kind == VarDeclKind::Const {
+            for declarator in &decl.decls {
+                if let (Pat::Ident(binding), Some(init)) = (&declarator.name, &declarator.init) {
+                    if let Some(value) = eval_string(self.constants, init) {
+                        self.constants.insert(binding.id.to_id(), value);
+                    }
+                }
+            }
+        }
+
+        decl.visit_children_with(self);
+    }
+}
 
 fn expression_to_name(expr: &Expr, ctx: &mut MacroCtx) -> String {
     let expr = unwrap_ts_only_expr(expr);
@@ -38,13 +154,12 @@ fn expression_to_value(expr: Box<Expr>) -> Box<Expr> {
     match unwrapped {
         Expr::Object(object) => {
             if object.props.len() > 1 {
-                HANDLER.with(|h| {
-                    h.struct_span_err(
-                        object.span,
-                        "Incorrect usage of a labeled expression. Expected exactly one property as `{variableName: variableValue}`.",
-                    )
-                    .emit();
-                });
+                Diagnostic::new(
+                    Code::InvalidLabeledExpression,
+                    object.span,
+                    "Incorrect usage of a labeled expression. Expected exactly one property as `{variableName: variableValue}`.",
+                )
+                .emit();
             }
 
             if let Some(PropOrSpread::Prop(prop)) = object.props.first() {
@@ -115,13 +230,12 @@ fn unwrap_ph_call(ctx: &MacroCtx, expr: Box<Expr>) -> Box<Expr> {
         }) {
             if let Some(first) = call.args.first() {
                 if !first.expr.is_object() {
-                    HANDLER.with(|h| {
-                        h.struct_span_err(
-                            first.expr.span(),
-                            "Incorrect usage of `ph` macro. First argument should be an object expression like `ph({name: value})`.",
-                        )
-                        .emit();
-                    });
+                    Diagnostic::new(
+                        Code::InvalidPhUsage,
+                        first.expr.span(),
+                        "Incorrect usage of `ph` macro. First argument should be an object expression like `ph({name: value})`.",
+                    )
+                    .emit();
                     return expr;
                 }
                 return first.expr.clone();
@@ -131,6 +245,14 @@ fn unwrap_ph_call(ctx: &MacroCtx, expr: Box<Expr>) -> Box<Expr> {
     expr
 }
 
+/// Placeholder for the expression, or its text when it's a compile-time string
+pub fn tokenize_expr_to_token(ctx: &mut MacroCtx, expr: Box<Expr>) -> MsgToken {
+    match ctx.transform.eval_string(&expr) {
+        Some(text) => MsgToken::String(text),
+        None => MsgToken::Arg(tokenize_expr_to_arg(ctx, expr)),
+    }
+}
+
 pub fn tokenize_expr_to_arg(ctx: &mut MacroCtx, expr: Box<Expr>) -> MsgArg {
     let expr = unwrap_ph_call(ctx, expr);
     tokenize_expression(expr, ctx)
@@ -156,8 +278,14 @@ pub fn tokenize_tpl(ctx: &mut MacroCtx, tpl: &Tpl) -> Vec<MsgToken> {
                 }
             }
 
-            let arg = tokenize_expr_to_arg(ctx, exp.clone());
-            tokens.push(MsgToken::Arg(arg));
+            if let Expr::Cond(cond) = exp.as_ref() {
+                if let Some(token) = try_tokenize_ternary(ctx, cond, tokenize_ternary_text_branch) {
+                    tokens.push(token);
+                    continue;
+                }
+            }
+
+            tokens.push(tokenize_expr_to_token(ctx, exp.clone()));
         }
     }
 
@@ -196,15 +324,146 @@ pub fn try_tokenize_call_expr_as_choice_cmp(
 
             return Some(vec![MsgToken::Arg(token_arg)]);
         } else {
-            // todo passed not an ObjectLiteral,
-            //      we should panic here or just skip this call
+            let name = ctx.transform.get_ident_export_name(ident).unwrap();
+            Diagnostic::new(
+                Code::InvalidChoiceCases,
+                arg.expr.span(),
+                format!("The cases of `{name}` must be an object literal."),
+            )
+            .help(format!("Write the cases inline, e.g. `{name}(count, {{ one: \"# book\", other: \"# books\" }})`. They are compiled into the message, so they can't come from a variable."))
+            .emit();
+        }
+    }
+
+    None
+}
+
+/// Tokens of a ternary branch, `None` when the branch can't be part of the message
+pub type TernaryBranchTokenizer = fn(&mut MacroCtx, &Expr) -> Option<Vec<MsgToken>>;
+
+/// Tokenize `cond ? "a" : "b"` as `{cond, select, true {a} other {b}}` when
+/// `ternary_to_select` is enabled. Otherwise, or when a branch is not
+/// supported, warn if the branches carry text which would stay untranslated
+/// and return `None`, so the caller keeps the ternary as a plain placeholder.
+pub fn try_tokenize_ternary(
+    ctx: &mut MacroCtx,
+    cond: &CondExpr,
+    tokenize_branch: TernaryBranchTokenizer,
+) -> Option<MsgToken> {
+    if ctx.transform.options.ternary_to_select {
+        // resolve the name first so placeholders are numbered in source order
+        let index = ctx.expression_index;
+        let name = expression_to_name(&cond.test, ctx);
+        let cons = tokenize_branch(ctx, &cond.cons);
+        let alt = tokenize_branch(ctx, &cond.alt);
+
+        if let (Some(cons), Some(alt)) = (cons, alt) {
+            return Some(MsgToken::Arg(MsgArg {
+                name,
+                value: ternary_select_value(&cond.test),
+                format: Some("select".into()),
+                cases: Some(vec![
+                    CaseOrOffset::Case(ChoiceCase {
+                        key: "true".into(),
+                        tokens: cons,
+                    }),
+                    CaseOrOffset::Case(ChoiceCase {
+                        key: "other".into(),
+                        tokens: alt,
+                    }),
+                ]),
+            }));
         }
+
+        // the ternary stays a single placeholder, numbered as if never split
+        ctx.expression_index = index;
+    }
+
+    if has_text_branch(&cond.cons) || has_text_branch(&cond.alt) {
+        Diagnostic::new(
+            Code::UntranslatedTernary,
+            cond.span,
+            "Text in this conditional is not part of the message and will not be translated.",
+        )
+        .help(if ctx.transform.options.ternary_to_select {
+            "Only string, template and JSX branches can be turned into a `select`."
+        } else {
+            "Enable the `ternaryToSelect` option to turn it into an ICU `select`, or use the `select` macro explicitly."
+        })
+        .emit();
     }
 
     None
 }
 
+/// Branch tokenizer for JS messages: strings, templates and nested ternaries
+pub fn tokenize_ternary_text_branch(ctx: &mut MacroCtx, expr: &Expr) -> Option<Vec<MsgToken>> {
+    match expr {
+        Expr::Paren(ParenExpr { expr, .. }) => tokenize_ternary_text_branch(ctx, expr),
+        Expr::Cond(cond) => {
+            try_tokenize_ternary(ctx, cond, tokenize_ternary_text_branch).map(|token| vec![token])
+        }
+        Expr::Lit(Lit::Str(_)) | Expr::Tpl(_) => try_tokenize_expr(ctx, expr),
+        _ => ctx
+            .transform
+            .eval_string(expr)
+            .map(|text| vec![MsgToken::String(text)]),
+    }
+}
+
+/// `select` matches the value as a string, so anything which is not already a
+/// boolean is coerced with `!!`
+fn ternary_select_value(test: &Expr) -> Box<Expr> {
+    let is_boolean = match unwrap_ts_only_expr(test) {
+        Expr::Lit(Lit::Bool(_)) => true,
+        Expr::Unary(UnaryExpr { op, .. }) => *op == UnaryOp::Bang,
+        Expr::Bin(BinExpr { op, .. }) => matches!(
+            op,
+            BinaryOp::EqEq
+                | BinaryOp::NotEq
+                | BinaryOp::EqEqEq
+                | BinaryOp::NotEqEq
+                | BinaryOp::Lt
+                | BinaryOp::LtEq
+                | BinaryOp::Gt
+                | BinaryOp::GtEq
+                | BinaryOp::InstanceOf
+                | BinaryOp::In
+        ),
+        _ => false,
+    };
+
+    if is_boolean {
+        return Box::new(test.clone());
+    }
+
+    let not = |arg: Box<Expr>| {
+        Box::new(Expr::Unary(UnaryExpr {
+            span: DUMMY_SP,
+            op: UnaryOp::Bang,
+            arg,
+        }))
+    };
+    not(not(Box::new(test.clone())))
+}
+
+/// Whether a ternary branch is a string or template with words in it
+fn has_text_branch(expr: &Expr) -> bool {
+    match expr {
+        Expr::Paren(ParenExpr { expr, .. }) => has_text_branch(expr),
+        Expr::Cond(cond) => has_text_branch(&cond.cons) || has_text_branch(&cond.alt),
+        Expr::Lit(Lit::Str(str)) => has_words(&str.value.to_string_lossy()),
+        Expr::Tpl(tpl) => tpl.quasis.iter().any(|quasi| has_words(&quasi.raw)),
+        _ => false,
+    }
+}
+
 pub fn try_tokenize_expr(ctx: &mut MacroCtx, expr: &Expr) -> Option<Vec<MsgToken>> {
+    // Constants and concatenations: PREFIX + " saved"
+    if let Some(text) = ctx.transform.eval_string(expr) {
+        return Some(vec![MsgToken::String(text)]);
+    }
+
     match expr {
         // String Literal: "has # friend"
         Expr::Lit(Lit::Str(str)) => Some(vec![MsgToken::String(
@@ -240,7 +499,7 @@ pub fn get_choice_cases_from_obj(
                         if let Expr::Lit(Lit::Num(Number { value, .. })) = prop.value.as_ref() {
                             choices.push(CaseOrOffset::Offset(value.to_string()))
                         } else {
-                            // todo: panic offset might be only a number, other forms is not supported
+                            report_invalid_offset(prop.span());
                         }
                     } else {
                         let tokens = try_tokenize_expr(ctx, &prop.value).unwrap_or_else(|| {
@@ -250,20 +509,72 @@ pub fn get_choice_cases_from_obj(
 
                         choices.push(CaseOrOffset::Case(ChoiceCase { tokens, key }));
                     }
+                } else {
+                    Diagnostic::new(
+                        Code::InvalidChoiceCases,
+                        prop.key.span(),
+                        "Unsupported case key.",
+                    )
+                    .help("Use a plural category or a value to match, e.g. `one`, `\"male\"` or `0` for an exact match.")
+                    .emit();
                 }
             } else {
-                // todo: panic here we could not parse anything else then KeyValue pair
+                Diagnostic::new(
+                    Code::InvalidChoiceCases,
+                    prop.span(),
+                    "Cases must be written as `key: value`.",
+                )
+                .help("Shorthand properties, methods and accessors are not supported. Write e.g. `other: \"# books\"`.")
+                .emit();
             }
         } else {
-            // todo: panic here, we could not parse spread
+            Diagnostic::new(
+                Code::InvalidChoiceCases,
+                prop_or_spread.span(),
+                "Spread is not supported in cases.",
+            )
+            .help("The cases are compiled into the message, so they have to be written out in the object literal.")
+            .emit();
         }
     }
 
     choices
 }
 
+/// The message in the runtime's compiled form, `None` when it's not valid ICU,
+/// which is reported by `validate_message`
+fn compiled_message_expr(message: &str) -> Option<Box<Expr>> {
+    let compiled = icu::compile_message(message).ok()?;
+    Some(json_to_expr(
+        &serde_json::to_value(compiled).expect("compiled messages are serializable"),
+    ))
+}
+
+/// `offset` of a plural is written into the message and has to be a whole number
+pub fn report_invalid_offset(span: Span) {
+    Diagnostic::new(Code::InvalidOffset, span, "`offset` must be a number.")
+        .help("Use a whole number literal, e.g. `offset: 1` or `offset=\"1\"`.")
+        .emit();
+}
+
 const LINGUI_T: &str = "t";
 
+/// Exports of the macro packages, other members of a namespace import don't exist at runtime
+const MACRO_EXPORTS: &[&str] = &[
+    LINGUI_T,
+    "msg",
+    "defineMessage",
+    "plural",
+    "select",
+    "selectOrdinal",
+    "ph",
+    "useLingui",
+    "Trans",
+    "Plural",
+    "Select",
+    "SelectOrdinal",
+];
+
 pub fn build_prefixed_id(
     options: &LinguiOptions,
     id: &str,
@@ -288,6 +599,12 @@ pub struct TransformCtx {
     symbol_to_id_map: HashMap<Atom, HashSet<Id>>,
     // local name -> export name
     id_to_symbol_map: HashMap<Id, Atom>,
+    // namespace or default import of a macro package -> context of the resolved members
+    macro_namespaces: HashMap<Id, SyntaxContext>,
+    // local name -> imported custom macro
+    custom_macros: HashMap<Id, CustomMacro>,
+    // `const` bindings of the file with a compile-time string value
+    string_constants: HashMap<Id, String>,
 
     pub should_add_18n_import: bool,
     pub should_add_trans_import: bool,
@@ -296,6 +613,25 @@ pub struct TransformCtx {
     pub options: LinguiOptions,
     pub directives: LinguiCommentDirectives,
     pub runtime_idents: RuntimeIdents,
+
+    /// set in extraction mode, receives every produced message descriptor
+    pub message_collector: Option<MessageCollector>,
+
+    /// used to resolve the line and column of `origin`
+    pub source_map: Option<Lrc<dyn SourceMapper>>,
+    /// file name reported in `origin`, falls back to the name in the source map
+    pub filename: Option<String>,
+
+    /// explicit ids used in the file, shared with the copies made for `useLingui` scopes
+    explicit_ids: Rc<RefCell<HashMap<String, ExplicitIdUse>>>,
+}
+
+/// Message and context an explicit id was first used with
+#[derive(Debug)]
+struct ExplicitIdUse {
+    message: String,
+    context: Option<String>,
+    span: Span,
 }
 
 #[derive(Clone)]
@@ -331,6 +667,132 @@ impl TransformCtx {
         self.directives.find_for_pos(pos)
     }
 
+    /// Message value replacing the source message in a descriptor. With an
+    /// inline catalog it's the compiled translation, or the compiled source
+    /// message when the catalog has no entry for `id`. With pseudo-localization
+    /// the source message is pseudo-localized first. With `messageOutput:
+    /// "compiled"` a kept message is compiled too.
+    pub fn message_override(&self, id: Option<&str>, message: Option<&str>) -> Option<Box<Expr>> {
+        let pseudo = message
+            .filter(|_| self.options.pseudo_localize)
+            .and_then(|message| icu::pseudo_localize(message).ok());
+        let message = pseudo.as_deref().or(message);
+
+        let Some(catalog) = self.options.inline_catalog.as_ref() else {
+            if self.options.message_output == MessageOutput::Compiled {
+                return message
+                    .filter(|_| {
+                        pseudo.is_some() || self.options.descriptor_fields.should_keep_message()
+                    })
+                    .and_then(compiled_message_expr);
+            }
+
+            return pseudo.map(|pseudo| Box::new(Expr::from(pseudo)));
+        };
+
+        if let Some(translation) = id.and_then(|id| catalog.get(id)) {
+            return Some(json_to_expr(translation));
+        }
+
+        compiled_message_expr(message?)
+    }
+
+    /// Output settings of the diagnostics reported while transforming this file
+    pub fn diagnostics_config(&self) -> DiagnosticsConfig {
+        DiagnosticsConfig {
+            format: self.options.diagnostics_format,
+            strict: self.options.strict,
+            severity: self.options.diagnostic_severity.clone(),
+            source_map: self.source_map.clone(),
+            filename: self.filename.clone(),
+        }
+    }
+
+    /// `origin: [file, line, column]` prop pointing at the macro in the source,
+    /// when `include_origin` is enabled. Line and column are 1-based.
+    pub fn origin_prop(&self, span: Span) -> Option<PropOrSpread> {
+        if !self.options.include_origin || span.is_dummy() {
+            return None;
+        }
+
+        let loc = self.source_map.as_ref()?.lookup_char_pos(span.lo);
+        let file = self
+            .filename
+            .clone()
+            .unwrap_or_else(|| loc.file.name.to_string());
+
+        Some(create_key_value_prop(
+            "origin",
+            json_to_expr(&serde_json::json!([file, loc.line, loc.col.0 + 1])),
+        ))
+    }
+
+    /// Report messages which are not valid ICU MessageFormat, they would only
+    /// fail at runtime otherwise.
+    pub fn validate_message(&self, message: &str, span: Span) {
+        match icu::parse(message) {
+            Ok(tokens) => {
+                check_plural_categories(&tokens, self.options.source_locale.as_deref(), span);
+            }
+            Err(err) => {
+                Diagnostic::new(
+                    Code::InvalidIcuSyntax,
+                    span,
+                    format!("Invalid ICU message syntax: {}.", err.message),
+                )
+                .help(format!("The message was `{message}`"))
+                .emit();
+            }
+        }
+    }
+
+    /// Report an explicit id reused in the same file with a different message
+    /// or context. Only one of them could end up in the catalog.
+    pub fn check_explicit_id(&self, id: &str, message: &str, context: Option<&str>, span: Span) {
+        let mut explicit_ids = self.explicit_ids.borrow_mut();
+
+        let Some(first) = explicit_ids.get(id) else {
+            explicit_ids.insert(
+                id.to_string(),
+                ExplicitIdUse {
+                    message: message.to_string(),
+                    context: context.map(str::to_string),
+                    span,
+                },
+            );
+            return;
+        };
+
+        let (kind, first_used_with) = if first.message != message {
+            ("message", format!("message `{}`", first.message))
+        } else if first.context.as_deref() != context {
+            let first_context = match &first.context {
+                Some(context) => format!("context `{context}`"),
+                None => "no context".to_string(),
+            };
+            ("context", first_context)
+        } else {
+            return;
+        };
+
+        Diagnostic::new(
+            Code::ConflictingMessageId,
+            span,
+            format!("Message id `{id}` is already used with a different {kind}."),
+        )
+        .related(
+            first.span,
+            format!("`{id}` was first used here with {first_used_with}"),
+        )
+        .emit();
+    }
+
+    pub fn collect_message(&self, message: ExtractedMessage) {
+        if let Some(collector) = &self.message_collector {
+            collector.push(message);
+        }
+    }
+
     /// is given ident exported from @lingui/macro? and one of choice functions?
     pub fn is_lingui_fn_choice_cmp(&self, ident: &Ident) -> bool {
         self.is_lingui_ident("plural", ident)
@@ -382,16 +844,235 @@ impl TransformCtx {
 
     pub fn register_macro_import(&mut self, imp: &ImportDecl) {
         for spec in &imp.specifiers {
-            if let ImportSpecifier::Named(spec) = spec {
-                if let Some(ModuleExportName::Ident(ident)) = &spec.imported {
-                    self.register_reference(&ident.sym, &spec.local.to_id());
-                } else {
-                    self.register_reference(&spec.local.sym, &spec.local.to_id());
+            match spec {
+                ImportSpecifier::Named(spec) => {
+                    if let Some(ModuleExportName::Ident(ident)) = &spec.imported {
+                        self.register_reference(&ident.sym, &spec.local.to_id());
+                    } else {
+                        self.register_reference(&spec.local.sym, &spec.local.to_id());
+                    }
+                }
+                // import * as m from "@lingui/core/macro"
+                // import m from "@lingui/core/macro"
+                ImportSpecifier::Namespace(ImportStarAsSpecifier { local, .. })
+                | ImportSpecifier::Default(ImportDefaultSpecifier { local, .. }) => {
+                    self.register_macro_namespace(local);
+                }
+            }
+        }
+    }
+
+    fn find_custom_macro(&self, package: &str, spec: &ImportSpecifier) -> Option<&CustomMacro> {
+        let ImportSpecifier::Named(spec) = spec else {
+            return None;
+        };
+        let name = match &spec.imported {
+            Some(ModuleExportName::Ident(ident)) => ident.sym.to_string(),
+            Some(ModuleExportName::Str(str)) => str.value.to_string_lossy().into_owned(),
+            None => spec.local.sym.to_string(),
+        };
+
+        self.options
+            .custom_macros
+            .iter()
+            .find(|custom| custom.package == package && custom.name == name)
+    }
+
+    pub fn has_custom_macro_imports(&self, imp: &ImportDecl) -> bool {
+        let package = imp.src.value.to_string_lossy();
+        imp.specifiers
+            .iter()
+            .any(|spec| self.find_custom_macro(&package, spec).is_some())
+    }
+
+    /// Register and drop the specifiers importing custom macros, other exports
+    /// of the package are kept. Returns false when nothing is left of the import.
+    pub fn take_custom_macro_imports(&mut self, imp: &mut ImportDecl) -> bool {
+        let package = imp.src.value.to_string_lossy().into_owned();
+
+        let specifiers = std::mem::take(&mut imp.specifiers);
+        for spec in specifiers {
+            match self.find_custom_macro(&package, &spec).cloned() {
+                Some(custom) => {
+                    self.custom_macros.insert(spec.local().to_id(), custom);
+                }
+                None => imp.specifiers.push(spec),
+            }
+        }
+
+        !imp.specifiers.is_empty()
+    }
+
+    /// Custom macro and local binding of a `require()` destructuring property,
+    /// `{ tc }` or `{ tc: local }`
+    fn find_custom_macro_prop(
+        &self,
+        package: &str,
+        prop: &ObjectPatProp,
+    ) -> Option<(&CustomMacro, Id)> {
+        let (name, local) = match prop {
+            ObjectPatProp::Assign(AssignPatProp {
+                key, value: None, ..
+            }) => (key.sym.to_string(), key.to_id()),
+            ObjectPatProp::KeyValue(KeyValuePatProp { key, value }) => {
+                let name = match key {
+                    PropName::Ident(name) => name.sym.to_string(),
+                    PropName::Str(name) => name.value.to_string_lossy().into_owned(),
+                    _ => return None,
+                };
+                let Pat::Ident(local) = &**value else {
+                    return None;
+                };
+                (name, local.id.to_id())
+            }
+            _ => return None,
+        };
+
+        self.options
+            .custom_macros
+            .iter()
+            .find(|custom| custom.package == package && custom.name == name)
+            .map(|custom| (custom, local))
+    }
+
+    pub fn has_custom_macro_require(&self, package: &str, pat: &Pat) -> bool {
+        let Pat::Object(obj) = pat else {
+            return false;
+        };
+        obj.props
+            .iter()
+            .any(|prop| self.find_custom_macro_prop(package, prop).is_some())
+    }
+
+    /// Register and drop the properties of `const { tc } = require("…")` which are
+    /// custom macros. Returns false when nothing is left of the pattern.
+    pub fn take_custom_macro_require(&mut self, package: &str, pat: &mut Pat) -> bool {
+        let Pat::Object(obj) = pat else {
+            return true;
+        };
+
+        let props = std::mem::take(&mut obj.props);
+        for prop in props {
+            match self
+                .find_custom_macro_prop(package, &prop)
+                .map(|(custom, local)| (custom.clone(), local))
+            {
+                Some((custom, local)) => {
+                    self.custom_macros.insert(local, custom);
+                }
+                None => obj.props.push(prop),
+            }
+        }
+
+        !obj.props.is_empty()
+    }
+
+    /// Remember the `const` bindings of the file which hold compile-time strings
+    pub fn register_string_constants<N>(&mut self, node: &N)
+    where
+        N: for<'a> VisitWith<StringConstantCollector<'a>>,
+    {
+        node.visit_with(&mut StringConstantCollector {
+            constants: &mut self.string_constants,
+        });
+    }
+
+    pub fn eval_string(&self, expr: &Expr) -> Option<String> {
+        eval_string(&self.string_constants, expr)
+    }
+
+    pub fn has_custom_macros(&self) -> bool {
+        !self.custom_macros.is_empty()
+    }
+
+    pub fn get_custom_macro(&self, ident: &Ident) -> Option<&CustomMacro> {
+        self.custom_macros.get(&ident.to_id())
+    }
+
+    /// Fresh ident registered as a built-in macro, for code expanded from custom macros
+    pub fn builtin_macro_ident(&mut self, export: &str, span: Span) -> Ident {
+        let ident = Ident::new(
+            export.into(),
+            span,
+            SyntaxContext::empty().apply_mark(Mark::new()),
+        );
+        self.register_reference(&ident.sym, &ident.to_id());
+        ident
+    }
+
+    /// const { t, plural: i18nPlural } = require("@lingui/core/macro")
+    /// const m = require("@lingui/core/macro")
+    pub fn register_macro_require(&mut self, pat: &Pat) {
+        match pat {
+            Pat::Ident(binding) => self.register_macro_namespace(&binding.id),
+            Pat::Object(obj) => {
+                for prop in &obj.props {
+                    match prop {
+                        ObjectPatProp::Assign(AssignPatProp {
+                            key, value: None, ..
+                        }) => {
+                            self.register_reference(&key.sym, &key.to_id());
+                        }
+                        ObjectPatProp::KeyValue(KeyValuePatProp { key, value }) => {
+                            let export: Option<Atom> = match key {
+                                PropName::Ident(name) => Some(name.sym.clone()),
+                                PropName::Str(name) => Some(name.value.to_string_lossy().into()),
+                                _ => None,
+                            };
+
+                            if let (Some(export), Pat::Ident(local)) = (export, &**value) {
+                                self.register_reference(&export, &local.id.to_id());
+                            }
+                        }
+                        _ => {}
+                    }
                 }
             }
+            _ => {}
         }
     }
 
+    pub fn register_macro_namespace(&mut self, local: &Ident) {
+        // members get a fresh context, so they can't clash with local bindings of the same name
+        self.macro_namespaces.insert(
+            local.to_id(),
+            SyntaxContext::empty().apply_mark(Mark::new()),
+        );
+    }
+
+    pub fn has_macro_namespaces(&self) -> bool {
+        !self.macro_namespaces.is_empty()
+    }
+
+    /// given import * as m from "@lingui/macro";
+    /// resolve_namespace_member(`m`, `t`) returns an ident registered as the `t` macro,
+    /// members which are not macros are reported
+    pub fn resolve_namespace_member(
+        &mut self,
+        namespace: &Ident,
+        name: &IdentName,
+        span: Span,
+    ) -> Option<Ident> {
+        let ctxt = *self.macro_namespaces.get(&namespace.to_id())?;
+        if !MACRO_EXPORTS.contains(&&*name.sym) {
+            Diagnostic::new(
+                Code::UnknownMacro,
+                span,
+                format!("`{}.{}` is not a Lingui macro.", namespace.sym, name.sym),
+            )
+            .help(format!(
+                "The macro import is removed during the build, so `{}` would not exist at runtime. Import it from a runtime package instead.",
+                namespace.sym
+            ))
+            .emit();
+            return None;
+        }
+
+        let ident = Ident::new(name.sym.clone(), span, ctxt);
+        self.register_reference(&name.sym, &ident.to_id());
+        Some(ident)
+    }
+
     /// Take a callee expression and detect is it a lingui t`` macro call
     /// Returns a callee object depending whether custom i18n instance was passed or not
     pub fn is_lingui_t_call_expr(&self, callee_expr: &Expr) -> (bool, Option<Box<Expr>>) {
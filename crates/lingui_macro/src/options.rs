@@ -1,5 +1,8 @@
+use crate::diagnostics::{Code, DiagnosticsFormat, Severity};
+use crate::generate_id::IdGeneration;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
 
 fn is_default<T: Default + PartialEq>(t: &T) -> bool {
     t == &T::default()
@@ -29,6 +32,17 @@ impl DescriptorFields {
     }
 }
 
+/// Form of the `message` field in descriptors
+#[derive(Deserialize, Serialize, Debug, PartialEq, Clone, Copy, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum MessageOutput {
+    /// ICU MessageFormat string, compiled by the runtime
+    #[default]
+    Icu,
+    /// compiled array form, so the runtime needs no message compiler
+    Compiled,
+}
+
 #[derive(Deserialize, Serialize, Debug, PartialEq, Clone, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct LinguiJsOptions {
@@ -50,6 +64,72 @@ pub struct LinguiJsOptions {
     pub jsx_placeholder_attribute: Option<String>,
     #[serde(default)]
     pub jsx_placeholder_defaults: Option<HashMap<String, String>>,
+    #[serde(default)]
+    pub inline_catalog: Option<BTreeMap<String, serde_json::Value>>,
+    #[serde(default)]
+    pub locales: Option<Vec<String>>,
+    #[serde(default)]
+    pub source_locale: Option<String>,
+    #[serde(default)]
+    pub pseudo_localize: Option<bool>,
+    #[serde(default)]
+    pub id_generation: Option<IdGeneration>,
+    #[serde(default)]
+    pub include_origin: Option<bool>,
+    #[serde(default)]
+    pub custom_macros: Option<Vec<CustomMacro>>,
+    #[serde(default)]
+    pub ternary_to_select: Option<bool>,
+    #[serde(default)]
+    pub lint_hardcoded_strings: Option<bool>,
+    #[serde(default)]
+    pub diagnostics_format: Option<DiagnosticsFormat>,
+    #[serde(default)]
+    pub strict: Option<bool>,
+    #[serde(default)]
+    pub diagnostic_severity: Option<BTreeMap<Code, Severity>>,
+    #[serde(default)]
+    pub message_output: Option<MessageOutput>,
+}
+
+/// A function exported from a user package which expands to a built-in macro,
+/// e.g. `tc(context, message)` or `tn(count, one, other)`
+#[derive(Deserialize, Serialize, Debug, PartialEq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CustomMacro {
+    pub name: String,
+    pub package: String,
+    #[serde(default)]
+    pub kind: CustomMacroKind,
+    /// role of each positional argument
+    pub args: Vec<CustomMacroArg>,
+}
+
+#[derive(Deserialize, Serialize, Debug, PartialEq, Clone, Copy, Default)]
+#[serde(rename_all = "camelCase")]
+pub enum CustomMacroKind {
+    /// translated immediately, like `t`
+    #[default]
+    T,
+    /// lazy message descriptor, like `msg`
+    Msg,
+}
+
+#[derive(Deserialize, Serialize, Debug, PartialEq, Clone, Copy)]
+#[serde(rename_all = "camelCase")]
+pub enum CustomMacroArg {
+    Message,
+    Context,
+    Comment,
+    Id,
+    /// value of a plural, its cases are the plural category arguments
+    Count,
+    Zero,
+    One,
+    Two,
+    Few,
+    Many,
+    Other,
 }
 
 #[derive(Deserialize, Serialize, Debug, PartialEq, Clone, Default)]
@@ -77,6 +157,21 @@ pub struct MacroPackagesConfig {
     pub jsx: Vec<String>,
 }
 
+impl CustomMacro {
+    /// plural category of a case argument
+    pub fn plural_case(arg: CustomMacroArg) -> Option<&'static str> {
+        Some(match arg {
+            CustomMacroArg::Zero => "zero",
+            CustomMacroArg::One => "one",
+            CustomMacroArg::Two => "two",
+            CustomMacroArg::Few => "few",
+            CustomMacroArg::Many => "many",
+            CustomMacroArg::Other => "other",
+            _ => return None,
+        })
+    }
+}
+
 impl MacroPackagesConfig {
     pub fn contains(&self, value: &str) -> bool {
         self.core.iter().any(|s| value == s.as_str())
@@ -123,6 +218,22 @@ impl LinguiJsOptions {
             id_prefix_leader: self.id_prefix_leader.clone(),
             jsx_placeholder_attribute: self.jsx_placeholder_attribute.clone(),
             jsx_placeholder_defaults: self.jsx_placeholder_defaults.clone(),
+            inline_catalog: self.inline_catalog.map(Arc::new),
+            pseudo_localize: self.pseudo_localize.unwrap_or(false),
+            id_generation: self.id_generation.unwrap_or_default(),
+            include_origin: self.include_origin.unwrap_or(false) && env_name != "production",
+            custom_macros: self.custom_macros.unwrap_or_default(),
+            ternary_to_select: self.ternary_to_select.unwrap_or(false),
+            lint_hardcoded_strings: self.lint_hardcoded_strings.unwrap_or(false),
+            diagnostics_format: self.diagnostics_format.unwrap_or_default(),
+            strict: self.strict.unwrap_or(false),
+            diagnostic_severity: self.diagnostic_severity.unwrap_or_default(),
+            message_output: self.message_output.unwrap_or_default(),
+            source_locale: self.source_locale.or_else(|| {
+                self.locales
+                    .as_ref()
+                    .and_then(|locales| locales.first().cloned())
+            }),
             macro_packages: MacroPackagesConfig {
                 core: self
                     .core_package
@@ -183,6 +294,15 @@ pub struct LinguiOptions {
     pub jsx_placeholder_attribute: Option<String>,
     #[serde(skip_serializing_if = "is_default")]
     pub jsx_placeholder_defaults: Option<HashMap<String, String>>,
+    /// compiled messages of a single locale, inlined into the descriptors
+    #[serde(skip_serializing_if = "is_default")]
+    pub inline_catalog: Option<Arc<BTreeMap<String, serde_json::Value>>>,
+    /// locale of the source messages, plural cases are checked against its CLDR categories
+    #[serde(skip_serializing_if = "is_default")]
+    pub source_locale: Option<String>,
+    /// emit pseudo-localized messages, ids are still generated from the source
+    #[serde(skip_serializing_if = "is_default")]
+    pub pseudo_localize: bool,
     #[serde(skip_serializing_if = "is_default")]
     pub macro_packages: MacroPackagesConfig,
     #[serde(skip_serializing_if = "is_default")]
@@ -190,6 +310,28 @@ pub struct LinguiOptions {
     #[serde(skip_serializing_if = "is_default")]
     pub use_lingui_v5_id_generation: bool,
     #[serde(skip_serializing_if = "is_default")]
+    pub id_generation: IdGeneration,
+    /// add `origin: [file, line, column]` to descriptors, never set in production
+    #[serde(skip_serializing_if = "is_default")]
+    pub include_origin: bool,
+    #[serde(skip_serializing_if = "is_default")]
+    pub custom_macros: Vec<CustomMacro>,
+    /// turn `cond ? "a" : "b"` inside messages into an ICU `select`
+    #[serde(skip_serializing_if = "is_default")]
+    pub ternary_to_select: bool,
+    /// warn about JSX text and user-facing attributes outside of macros
+    #[serde(skip_serializing_if = "is_default")]
+    pub lint_hardcoded_strings: bool,
+    #[serde(skip_serializing_if = "is_default")]
+    pub diagnostics_format: DiagnosticsFormat,
+    /// report warnings as errors
+    #[serde(skip_serializing_if = "is_default")]
+    pub strict: bool,
+    #[serde(skip_serializing_if = "is_default")]
+    pub diagnostic_severity: BTreeMap<Code, Severity>,
+    #[serde(skip_serializing_if = "is_default")]
+    pub message_output: MessageOutput,
+    #[serde(skip_serializing_if = "is_default")]
     pub use_jsdoc_i18n_comment: bool,
 }
 
@@ -202,12 +344,32 @@ impl Default for LinguiOptions {
             id_prefix_leader: None,
             jsx_placeholder_attribute: None,
             jsx_placeholder_defaults: None,
+            inline_catalog: None,
+            source_locale: None,
+            pseudo_localize: false,
+            id_generation: Default::default(),
+            include_origin: false,
+            custom_macros: vec![],
+            ternary_to_select: false,
+            lint_hardcoded_strings: false,
+            diagnostics_format: DiagnosticsFormat::Text,
+            strict: false,
+            diagnostic_severity: BTreeMap::new(),
+            message_output: MessageOutput::Icu,
             macro_packages: Default::default(),
             runtime_modules: Default::default(),
         }
     }
 }
 
+impl LinguiOptions {
+    /// Id for a message without an explicit id, see [`IdGeneration`]
+    pub fn generate_message_id(&self, message: &str, context: &str) -> String {
+        self.id_generation
+            .generate(message, context, self.use_lingui_v5_id_generation)
+    }
+}
+
 #[cfg(test)]
 mod lib_tests {
     use super::*;
@@ -249,6 +411,19 @@ mod lib_tests {
                 id_prefix_leader: None,
                 jsx_placeholder_attribute: None,
                 jsx_placeholder_defaults: None,
+                inline_catalog: None,
+                locales: None,
+                source_locale: None,
+                pseudo_localize: None,
+                id_generation: None,
+                include_origin: None,
+                custom_macros: None,
+                ternary_to_select: None,
+                lint_hardcoded_strings: None,
+                diagnostics_format: None,
+                strict: None,
+                diagnostic_severity: None,
+                message_output: None,
                 use_jsdoc_i18n_comment: None,
             }
         )
@@ -281,6 +456,19 @@ mod lib_tests {
                 id_prefix_leader: None,
                 jsx_placeholder_attribute: None,
                 jsx_placeholder_defaults: None,
+                inline_catalog: None,
+                locales: None,
+                source_locale: None,
+                pseudo_localize: None,
+                id_generation: None,
+                include_origin: None,
+                custom_macros: None,
+                ternary_to_select: None,
+                lint_hardcoded_strings: None,
+                diagnostics_format: None,
+                strict: None,
+                diagnostic_severity: None,
+                message_output: None,
                 use_jsdoc_i18n_comment: None,
                 core_package: None,
                 jsx_package: None,
@@ -314,6 +502,19 @@ mod lib_tests {
                 id_prefix_leader: None,
                 jsx_placeholder_attribute: None,
                 jsx_placeholder_defaults: None,
+                inline_catalog: None,
+                locales: None,
+                source_locale: None,
+                pseudo_localize: None,
+                id_generation: None,
+                include_origin: None,
+                custom_macros: None,
+                ternary_to_select: None,
+                lint_hardcoded_strings: None,
+                diagnostics_format: None,
+                strict: None,
+                diagnostic_severity: None,
+                message_output: None,
                 use_jsdoc_i18n_comment: None,
             }
         )
@@ -452,6 +653,46 @@ mod lib_tests {
         assert_eq!(defaults.get("em").unwrap(), "emphasis");
     }
 
+    #[test]
+    fn test_inline_catalog_config() {
+        let config = serde_json::from_str::<LinguiJsOptions>(
+            r#"{
+                "inlineCatalog": {
+                    "OVaF9k": ["Ahoj ", ["name"]]
+                }
+               }"#,
+        )
+        .unwrap();
+
+        let options = config.into_options("production");
+        let catalog = options.inline_catalog.unwrap();
+        assert_eq!(catalog["OVaF9k"], serde_json::json!(["Ahoj ", ["name"]]));
+    }
+
+    #[test]
+    fn test_source_locale_config() {
+        let config = serde_json::from_str::<LinguiJsOptions>(
+            r#"{
+                "locales": ["en", "cs"]
+               }"#,
+        )
+        .unwrap();
+
+        let options = config.into_options("development");
+        assert_eq!(options.source_locale.as_deref(), Some("en"));
+
+        let config = serde_json::from_str::<LinguiJsOptions>(
+            r#"{
+                "locales": ["en", "cs"],
+                "sourceLocale": "cs"
+               }"#,
+        )
+        .unwrap();
+
+        let options = config.into_options("development");
+        assert_eq!(options.source_locale.as_deref(), Some("cs"));
+    }
+
     #[test]
     fn test_descriptor_fields_explicit_auto() {
         let config = serde_json::from_str::<LinguiJsOptions>(
@@ -567,4 +808,138 @@ mod lib_tests {
             ("@lingui/react".into(), "useLingui".into())
         );
     }
+
+    #[test]
+    fn test_id_generation_config() {
+        let config = serde_json::from_str::<LinguiJsOptions>(
+            r#"{
+                "idGeneration": { "algorithm": "sha512", "length": 8, "salt": "app" }
+               }"#,
+        )
+        .unwrap();
+
+        let options = config.into_options("development");
+        assert_eq!(
+            options.id_generation,
+            IdGeneration {
+                algorithm: crate::generate_id::HashAlgorithm::Sha512,
+                length: 8,
+                salt: Some("app".into()),
+                ..Default::default()
+            }
+        );
+
+        let config = serde_json::from_str::<LinguiJsOptions>(
+            r#"{
+                "idGeneration": { "strategy": "message" }
+               }"#,
+        )
+        .unwrap();
+
+        let options = config.into_options("development");
+        assert_eq!(options.generate_message_id("Hello", ""), "Hello");
+    }
+
+    #[test]
+    fn test_include_origin_config() {
+        let config = serde_json::from_str::<LinguiJsOptions>(
+            r#"{
+                "includeOrigin": true
+               }"#,
+        )
+        .unwrap();
+
+        assert!(config.clone().into_options("development").include_origin);
+        assert!(!config.into_options("production").include_origin);
+    }
+
+    #[test]
+    fn test_diagnostics_format_config() {
+        let config = serde_json::from_str::<LinguiJsOptions>(
+            r#"{
+                "diagnosticsFormat": "json"
+               }"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            config.into_options("development").diagnostics_format,
+            DiagnosticsFormat::Json
+        );
+    }
+
+    #[test]
+    fn test_message_output_config() {
+        let config =
+            serde_json::from_str::<LinguiJsOptions>(r#"{ "messageOutput": "compiled" }"#).unwrap();
+
+        assert_eq!(
+            config.into_options("development").message_output,
+            MessageOutput::Compiled
+        );
+        assert_eq!(LinguiOptions::default().message_output, MessageOutput::Icu);
+    }
+
+    #[test]
+    fn test_diagnostic_severity_config() {
+        let config = serde_json::from_str::<LinguiJsOptions>(
+            r#"{
+                "strict": true,
+                "diagnosticSeverity": { "LINGUI019": "off", "invalid-icu-syntax": "warning" }
+               }"#,
+        )
+        .unwrap();
+
+        let options = config.into_options("development");
+        assert!(options.strict);
+        assert_eq!(
+            options.diagnostic_severity,
+            BTreeMap::from([
+                (Code::InvalidIcuSyntax, Severity::Warning),
+                (Code::HardcodedString, Severity::Off),
+            ])
+        );
+
+        let error = serde_json::from_str::<LinguiJsOptions>(
+            r#"{ "diagnosticSeverity": { "no-such-check": "off" } }"#,
+        )
+        .unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("unknown diagnostic `no-such-check`"));
+    }
+
+    #[test]
+    fn test_custom_macros_config() {
+        let config = serde_json::from_str::<LinguiJsOptions>(
+            r#"{
+                "customMacros": [
+                    { "name": "tc", "package": "@acme/i18n", "args": ["context", "message"] },
+                    { "name": "msgn", "package": "@acme/i18n", "kind": "msg", "args": ["count", "one", "other"] }
+                ]
+               }"#,
+        )
+        .unwrap();
+
+        let options = config.into_options("development");
+        assert_eq!(options.custom_macros[0].kind, CustomMacroKind::T);
+        assert_eq!(
+            options.custom_macros[1],
+            CustomMacro {
+                name: "msgn".into(),
+                package: "@acme/i18n".into(),
+                kind: CustomMacroKind::Msg,
+                args: vec![
+                    CustomMacroArg::Count,
+                    CustomMacroArg::One,
+                    CustomMacroArg::Other
+                ],
+            }
+        );
+
+        assert!(serde_json::from_str::<LinguiJsOptions>(
+            r#"{ "customMacros": [{ "name": "x", "package": "y", "args": ["unknown"] }] }"#
+        )
+        .is_err());
+    }
 }
@@ -0,0 +1,104 @@
+//! Plural categories per language from the CLDR plural rules.
+//!
+//! Only the set of categories is kept, which is all that's needed to check the
+//! cases written in the source messages. Languages are matched by their
+//! primary subtag, so `en-US` and `en_GB` resolve to `en`.
+
+const OTHER: &[&str] = &["other"];
+const ONE: &[&str] = &["one", "other"];
+const ONE_MANY: &[&str] = &["one", "many", "other"];
+const ONE_TWO: &[&str] = &["one", "two", "other"];
+const ONE_FEW: &[&str] = &["one", "few", "other"];
+const ONE_TWO_FEW: &[&str] = &["one", "two", "few", "other"];
+const ONE_FEW_MANY: &[&str] = &["one", "few", "many", "other"];
+const ONE_TWO_FEW_MANY: &[&str] = &["one", "two", "few", "many", "other"];
+const ONE_TWO_MANY: &[&str] = &["one", "two", "many", "other"];
+const ZERO_ONE: &[&str] = &["zero", "one", "other"];
+const FEW: &[&str] = &["few", "other"];
+const MANY: &[&str] = &["many", "other"];
+const ALL: &[&str] = &["zero", "one", "two", "few", "many", "other"];
+
+fn language(locale: &str) -> String {
+    locale
+        .split(['-', '_'])
+        .next()
+        .unwrap_or_default()
+        .to_ascii_lowercase()
+}
+
+/// Categories used by `plural` in the given locale, `None` for unknown locales
+pub fn cardinal_categories(locale: &str) -> Option<&'static [&'static str]> {
+    Some(match language(locale).as_str() {
+        "bm" | "bo" | "dz" | "hnj" | "id" | "ig" | "ii" | "in" | "ja" | "jbo" | "jv" | "jw"
+        | "kde" | "kea" | "km" | "ko" | "lkt" | "lo" | "ms" | "my" | "nqo" | "osa" | "root"
+        | "sah" | "ses" | "sg" | "su" | "th" | "to" | "tpi" | "vi" | "wo" | "yo" | "yue" | "zh" => {
+            OTHER
+        }
+        "af" | "ak" | "am" | "an" | "as" | "asa" | "ast" | "az" | "bal" | "bem" | "bez" | "bg"
+        | "bho" | "bn" | "brx" | "ce" | "ceb" | "cgg" | "chr" | "ckb" | "da" | "de" | "doi"
+        | "dv" | "ee" | "el" | "en" | "eo" | "et" | "eu" | "fa" | "ff" | "fi" | "fil" | "fo"
+        | "fur" | "fy" | "gl" | "gsw" | "gu" | "guw" | "ha" | "haw" | "hi" | "hu" | "hy" | "ia"
+        | "io" | "is" | "ji" | "jgo" | "jmc" | "ka" | "kab" | "kaj" | "kcg" | "kk" | "kkj"
+        | "kl" | "kn" | "ks" | "ksb" | "ku" | "ky" | "lb" | "lg" | "lij" | "ln" | "mas" | "mg"
+        | "mgo" | "mk" | "ml" | "mn" | "mr" | "nah" | "nb" | "nd" | "ne" | "nl" | "nn" | "nnh"
+        | "no" | "nr" | "nso" | "ny" | "nyn" | "om" | "or" | "os" | "pa" | "pap" | "pcm" | "ps"
+        | "rm" | "rof" | "rwk" | "saq" | "sc" | "sd" | "sdh" | "seh" | "si" | "sn" | "so"
+        | "sq" | "ss" | "ssy" | "st" | "sv" | "sw" | "syr" | "ta" | "te" | "teo" | "ti" | "tig"
+        | "tk" | "tl" | "tn" | "tr" | "ts" | "tzm" | "ug" | "ur" | "uz" | "ve" | "vo" | "vun"
+        | "wa" | "wae" | "xh" | "xog" | "yi" | "zu" => ONE,
+        "ca" | "es" | "fr" | "it" | "lld" | "pt" | "scn" | "vec" => ONE_MANY,
+        "he" | "iu" | "iw" | "naq" | "sat" | "se" | "sma" | "smi" | "smj" | "smn" | "sms" => {
+            ONE_TWO
+        }
+        "bs" | "hr" | "mo" | "ro" | "sh" | "shi" | "sr" => ONE_FEW,
+        "dsb" | "gd" | "hsb" | "sl" => ONE_TWO_FEW,
+        "be" | "cs" | "lt" | "pl" | "ru" | "sk" | "uk" => ONE_FEW_MANY,
+        "br" | "ga" | "gv" | "mt" => ONE_TWO_FEW_MANY,
+        "ksh" | "lag" | "lv" | "prg" => ZERO_ONE,
+        "ar" | "ars" | "cy" | "kw" => ALL,
+        _ => return None,
+    })
+}
+
+/// Categories used by `selectordinal` in the given locale, `None` for unknown locales
+pub fn ordinal_categories(locale: &str) -> Option<&'static [&'static str]> {
+    Some(match language(locale).as_str() {
+        "af" | "am" | "an" | "ar" | "bg" | "bs" | "ce" | "cs" | "da" | "de" | "dsb" | "el"
+        | "es" | "et" | "eu" | "fa" | "fi" | "fy" | "gl" | "gsw" | "he" | "hr" | "hsb" | "ia"
+        | "id" | "in" | "is" | "iw" | "ja" | "km" | "kn" | "ko" | "ky" | "lt" | "lv" | "ml"
+        | "mn" | "my" | "nb" | "nl" | "no" | "pa" | "pl" | "prg" | "ps" | "pt" | "root" | "ru"
+        | "sd" | "sh" | "si" | "sk" | "sl" | "sr" | "sw" | "ta" | "te" | "th" | "tpi" | "tr"
+        | "ur" | "uz" | "yue" | "zh" | "zu" => OTHER,
+        "bal" | "fil" | "fr" | "ga" | "hy" | "lo" | "mo" | "ms" | "ro" | "tl" | "vi" | "hu"
+        | "ne" | "sv" => ONE,
+        "it" | "kk" | "lij" | "sc" | "scn" | "vec" => MANY,
+        "ka" | "kw" | "sq" => ONE_MANY,
+        "be" | "tk" | "uk" => FEW,
+        "ca" | "en" => ONE_TWO_FEW,
+        "mk" => ONE_TWO_MANY,
+        "az" => ONE_FEW_MANY,
+        "as" | "bn" | "gu" | "hi" | "or" => ONE_TWO_FEW_MANY,
+        "cy" => ALL,
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_primary_language_subtag() {
+        assert_eq!(cardinal_categories("en-US"), Some(ONE));
+        assert_eq!(cardinal_categories("pt_BR"), Some(ONE_MANY));
+        assert_eq!(cardinal_categories("CS"), Some(ONE_FEW_MANY));
+        assert_eq!(cardinal_categories("xx"), None);
+    }
+
+    #[test]
+    fn resolves_ordinal_categories() {
+        assert_eq!(ordinal_categories("en"), Some(ONE_TWO_FEW));
+        assert_eq!(ordinal_categories("de"), Some(OTHER));
+        assert_eq!(ordinal_categories("cy"), Some(ALL));
+    }
+}
@@ -1,3 +1,4 @@
+use swc_core::common::Span;
 use swc_core::ecma::ast::{Expr, JSXOpeningElement};
 use swc_core::ecma::atoms::Atom;
 
@@ -5,7 +6,8 @@ pub enum MsgToken {
     String(String),
     Arg(MsgArg),
     TagOpening(TagOpening),
-    TagClosing,
+    /// span of the closing tag, used to report tags without an opening
+    TagClosing(Span),
 }
 
 pub struct MsgArg {
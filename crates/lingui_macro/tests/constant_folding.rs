@@ -0,0 +1,33 @@
+#[macro_use]
+mod common;
+
+to!(
+    js_inlines_string_constants,
+    r##"
+     import { t, plural } from "@lingui/core/macro";
+
+     const PREFIX = "Document";
+     const SUFFIX = ` was ${"saved"}`;
+     const FULL = PREFIX + " " + "name" as const;
+     let mutable = "mutable";
+
+     t({ message: PREFIX + " saved" });
+     t`${PREFIX} ${"by"} ${user}`;
+     t`${FULL}${SUFFIX} ${mutable}`;
+     plural(count, { one: PREFIX, other: `${count} ${PREFIX}s` });
+     "##
+);
+
+to!(
+    jsx_inlines_string_constants,
+    r##"
+     import { Trans, Plural } from "@lingui/react/macro";
+
+     const APP = "Acme";
+
+     function App({ APP: shadowed }) {
+       return <Trans>Welcome to {APP + "!"} {shadowed}</Trans>;
+     }
+     <Plural value={count} one={APP} other="# apps" />;
+     "##
+);
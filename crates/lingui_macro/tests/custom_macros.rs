@@ -0,0 +1,109 @@
+use lingui_macro::{CustomMacro, CustomMacroArg, CustomMacroKind, LinguiOptions};
+
+#[macro_use]
+mod common;
+
+to!(
+    custom_macros_expand_to_builtin_macros,
+    LinguiOptions {
+        custom_macros: vec![
+            CustomMacro {
+                name: "tc".into(),
+                package: "@acme/i18n".into(),
+                kind: CustomMacroKind::T,
+                args: vec![CustomMacroArg::Context, CustomMacroArg::Message],
+            },
+            CustomMacro {
+                name: "tn".into(),
+                package: "@acme/i18n".into(),
+                kind: CustomMacroKind::T,
+                args: vec![
+                    CustomMacroArg::Count,
+                    CustomMacroArg::One,
+                    CustomMacroArg::Other,
+                ],
+            },
+            CustomMacro {
+                name: "msgc".into(),
+                package: "@acme/i18n".into(),
+                kind: CustomMacroKind::Msg,
+                args: vec![
+                    CustomMacroArg::Id,
+                    CustomMacroArg::Message,
+                    CustomMacroArg::Comment,
+                ],
+            },
+        ],
+        ..Default::default()
+    },
+    r##"
+     import { tc, tn as plural, msgc, formatDate } from "@acme/i18n";
+
+     tc("verb", `Open ${file}`);
+     plural(count, "# file", "# files");
+     const descriptor = msgc("menu.open", "Open", "Menu item");
+     formatDate(date);
+     "##
+);
+
+to!(
+    custom_macros_drop_import_of_only_macros,
+    LinguiOptions {
+        custom_macros: vec![CustomMacro {
+            name: "tc".into(),
+            package: "@acme/i18n".into(),
+            kind: CustomMacroKind::T,
+            args: vec![CustomMacroArg::Context, CustomMacroArg::Message],
+        },],
+        ..Default::default()
+    },
+    r##"
+     import { tc } from "@acme/i18n";
+     import { Trans } from "@lingui/react/macro";
+
+     <Trans>Hello {tc("greeting", "world")}</Trans>;
+     "##
+);
+
+to_panic!(
+    custom_macros_check_argument_count,
+    LinguiOptions {
+        custom_macros: vec![CustomMacro {
+            name: "tn".into(),
+            package: "@acme/i18n".into(),
+            kind: CustomMacroKind::T,
+            args: vec![
+                CustomMacroArg::Count,
+                CustomMacroArg::One,
+                CustomMacroArg::Other,
+            ],
+        },],
+        ..Default::default()
+    },
+    r##"
+     import { tn } from "@acme/i18n";
+
+     tn(count, "# file");
+     "##
+);
+
+to!(
+    custom_macros_expand_in_commonjs_scripts,
+    LinguiOptions {
+        custom_macros: vec![CustomMacro {
+            name: "tc".into(),
+            package: "@acme/i18n".into(),
+            kind: CustomMacroKind::T,
+            args: vec![CustomMacroArg::Context, CustomMacroArg::Message],
+        }],
+        ..Default::default()
+    },
+    r##"
+     const { tc, formatDate } = require("@acme/i18n");
+     const { tc: translate } = require("@acme/i18n");
+
+     tc("verb", `Open ${file}`);
+     translate("noun", "Open");
+     formatDate(date);
+     "##
+);
@@ -0,0 +1,63 @@
+use lingui_macro::diagnostics::{Code, DiagnosticsFormat, Severity};
+use lingui_macro::LinguiOptions;
+use std::collections::BTreeMap;
+
+#[macro_use]
+mod common;
+
+to_panic!(
+    diagnostics_as_json,
+    LinguiOptions {
+        diagnostics_format: DiagnosticsFormat::Json,
+        source_locale: Some("en".into()),
+        ..Default::default()
+    },
+    r##"
+     import { t, plural } from "@lingui/core/macro";
+
+     t({ id: "save", message: "Save" });
+     t({ id: "save", message: "Save changes" });
+     t`${plural(count, { one: "# item", few: "# items", other: "# items" })}`;
+     "##
+);
+
+to_panic!(
+    strict_reports_warnings_as_errors,
+    LinguiOptions {
+        strict: true,
+        ..Default::default()
+    },
+    r##"
+     import { Plural } from "@lingui/react/macro";
+
+     <Plural {...props} one="# book" other="# books" />;
+     "##
+);
+
+to!(
+    diagnostic_severity_off,
+    LinguiOptions {
+        strict: true,
+        diagnostic_severity: BTreeMap::from([(Code::UnsupportedSpread, Severity::Off)]),
+        ..Default::default()
+    },
+    r##"
+     import { Plural } from "@lingui/react/macro";
+
+     <Plural {...props} value={count} one="# book" other="# books" />;
+     "##
+);
+
+to_panic!(
+    diagnostic_severity_override,
+    LinguiOptions {
+        diagnostic_severity: BTreeMap::from([(Code::DuplicatePlaceholder, Severity::Warning)]),
+        jsx_placeholder_attribute: Some("_t".into()),
+        ..Default::default()
+    },
+    r#"
+     import { Trans } from "@lingui/react/macro";
+
+     <Trans><a href="/a" _t="link">A</a> <a href="/b" _t="link">B</a></Trans>;
+     "#
+);
@@ -0,0 +1,52 @@
+#[macro_use]
+mod common;
+
+to_panic!(
+    conflicting_message_in_t,
+    Default::default(),
+    r##"
+     import { t } from "@lingui/core/macro";
+
+     t({ id: "save", message: "Save" });
+     t({ id: "save", message: "Save draft" });
+     "##
+);
+
+to_panic!(
+    conflicting_context_in_define_message,
+    Default::default(),
+    r##"
+     import { defineMessage } from "@lingui/core/macro";
+
+     const a = defineMessage({ id: "open", message: "Open", context: "verb" });
+     const b = defineMessage({ id: "open", message: "Open" });
+     "##
+);
+
+to_panic!(
+    conflicting_message_across_js_and_jsx,
+    Default::default(),
+    r##"
+     import { t } from "@lingui/core/macro";
+     import { Trans, useLingui } from "@lingui/react/macro";
+
+     function Button() {
+       const { t } = useLingui();
+       return <button title={t({ id: "save", message: "Save" })}><Trans id="save">Save {name}</Trans></button>;
+     }
+     "##
+);
+
+to!(
+    same_explicit_id_with_same_message,
+    r##"
+     import { t, defineMessage } from "@lingui/core/macro";
+     import { Trans } from "@lingui/react/macro";
+
+     t({ id: "save", message: "Save" });
+     defineMessage({ id: "save", message: "Save" });
+     t({ id: "save" });
+     <Trans id="save">Save</Trans>;
+     <Trans id="save" />;
+     "##
+);
@@ -0,0 +1,140 @@
+use lingui_macro::{DescriptorFields, LinguiOptions, MessageCollector};
+use swc_core::common::sync::Lrc;
+use swc_core::common::{SourceMap, SourceMapper};
+
+#[macro_use]
+mod common;
+
+/// Run the transform in extraction mode and render the collected messages
+/// with their origin resolved to `line:column`.
+fn extract(options: LinguiOptions, input: &str) -> String {
+    let source = common::dedent(input);
+    let collector = MessageCollector::new();
+    let mut source_map: Option<Lrc<SourceMap>> = None;
+
+    common::transform(source.as_str(), |comments, cm| {
+        source_map = Some(cm.clone());
+        swc_core::ecma::visit::fold_pass(
+            lingui_macro::LinguiMacroFolder::new(
+                options,
+                Some(comments.clone()),
+                cm as Lrc<dyn SourceMapper>,
+            )
+            .with_message_collector(collector.clone()),
+        )
+    })
+    .expect("Transform produced unexpected errors");
+
+    let cm = source_map.unwrap();
+
+    let messages: Vec<String> = collector
+        .take()
+        .into_iter()
+        .map(|msg| {
+            let loc = cm.lookup_char_pos(msg.origin.lo);
+            format!(
+                "id: {:?}\nmessage: {:?}\ncontext: {:?}\ncomment: {:?}\nplaceholders: {:?}\ncomponents: {:?}\norigin: {}:{}",
+                msg.id,
+                msg.message,
+                msg.context,
+                msg.comment,
+                msg.placeholders,
+                msg.components,
+                loc.line,
+                loc.col_display + 1,
+            )
+        })
+        .collect();
+
+    format!("{source}\n\n↓ ↓ ↓ ↓ ↓ ↓\n\n{}", messages.join("\n\n"))
+}
+
+#[test]
+fn extract_js_macros() {
+    insta::assert_snapshot!(extract(
+        Default::default(),
+        r##"
+        import { t, plural, msg, defineMessage } from "@lingui/core/macro";
+
+        t`Hello ${name}`;
+        t({ message: "Saved", context: "toast", comment: "shown after save" });
+        plural(count, { one: "# book", other: "# books" });
+        msg`Lazy`;
+        defineMessage({ id: "explicit.id", message: "With explicit id" });
+        defineMessage({ id: "only.id" });
+        "##
+    ));
+}
+
+#[test]
+fn extract_jsx_macros() {
+    insta::assert_snapshot!(extract(
+        Default::default(),
+        r##"
+        import { Trans, Plural } from "@lingui/react/macro";
+
+        <Trans comment="greeting">Hello <b>{name}</b></Trans>;
+        <Trans id="custom.id" context="menu">Open</Trans>;
+        <Trans id="only.id" />;
+        <Plural value={count} one="# item" other="# items" />;
+        "##
+    ));
+}
+
+#[test]
+fn extract_ignores_descriptor_fields() {
+    insta::assert_snapshot!(extract(
+        LinguiOptions {
+            descriptor_fields: DescriptorFields::IdOnly,
+            ..Default::default()
+        },
+        r##"
+        import { t } from "@lingui/core/macro";
+
+        t({ message: "Saved", context: "toast", comment: "shown after save" });
+        "##
+    ));
+}
+
+#[test]
+fn extract_with_directives() {
+    insta::assert_snapshot!(extract(
+        Default::default(),
+        r##"
+        import { t } from "@lingui/core/macro";
+        import { Trans } from "@lingui/react/macro";
+
+        // lingui-set context="settings" comment="from directive" idPrefix="settings."
+        t`Save`;
+        t({ id: "title", message: "Title" });
+        <Trans>Close</Trans>;
+        "##
+    ));
+}
+
+#[test]
+fn extract_inside_use_lingui() {
+    insta::assert_snapshot!(extract(
+        Default::default(),
+        r##"
+        import { useLingui } from "@lingui/react/macro";
+
+        function Component() {
+          const { t } = useLingui();
+          return t`Inside component`;
+        }
+        "##
+    ));
+}
+
+#[test]
+fn extract_skips_dynamic_ids() {
+    insta::assert_snapshot!(extract(
+        Default::default(),
+        r##"
+        import { t } from "@lingui/core/macro";
+
+        t({ id: dynamicId, message: "Dynamic" });
+        "##
+    ));
+}
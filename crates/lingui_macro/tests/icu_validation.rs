@@ -0,0 +1,54 @@
+#[macro_use]
+mod common;
+
+to_panic!(
+    invalid_raw_message_in_define_message,
+    Default::default(),
+    r##"
+     import { defineMessage } from "@lingui/core/macro";
+
+     const msg = defineMessage({ id: "books", message: "{count, plural, one {#} other {#}" });
+     "##
+);
+
+to_panic!(
+    invalid_raw_message_in_t,
+    Default::default(),
+    r##"
+     import { t } from "@lingui/core/macro";
+
+     t({ message: "Hello {name" });
+     "##
+);
+
+to_panic!(
+    generated_plural_without_other,
+    Default::default(),
+    r##"
+     import { plural } from "@lingui/core/macro";
+
+     plural(count, { one: "# book" });
+     "##
+);
+
+to_panic!(
+    invalid_jsx_message,
+    Default::default(),
+    r##"
+     import { Trans } from "@lingui/react/macro";
+
+     <Trans>Closing {"}"} brace</Trans>;
+     "##
+);
+
+to!(
+    valid_messages_pass,
+    r##"
+     import { t, defineMessage } from "@lingui/core/macro";
+     import { Trans } from "@lingui/react/macro";
+
+     defineMessage({ message: "{count, plural, =0 {None} one {# book} other {# books}}" });
+     t`It's '{quoted}'`;
+     <Trans>Hello <b>{name}</b></Trans>;
+     "##
+);
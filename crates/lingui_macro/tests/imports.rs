@@ -175,3 +175,104 @@ namespace Bar {
 const afterNamespace = t`after namespace`;
      "#
 );
+
+to!(
+    js_should_resolve_namespace_import,
+    r##"
+     import * as m from "@lingui/core/macro";
+
+     const t = "not a macro";
+     m.t`Hello ${name}`;
+     m.t(customI18n)`With i18n`;
+     const descriptor = m.msg`Message`;
+     const other = m.defineMessage({ message: "Defined" });
+     m.plural(count, { one: "# book", other: "# books" });
+     m.t`You have ${m.plural(count, { one: "# book", other: "# books" })}`;
+     "##
+);
+
+to!(
+    jsx_should_resolve_namespace_import,
+    r##"
+     import * as macro from "@lingui/react/macro";
+
+     function App() {
+       const { t } = macro.useLingui();
+       return (
+         <div title={t`Title`}>
+           <macro.Trans>Hello <b>{name}</b></macro.Trans>
+           <macro.Plural value={count} one="# book" other="# books" />
+         </div>
+       );
+     }
+     "##
+);
+
+to!(
+    should_resolve_default_import,
+    r##"
+     import lingui from "@lingui/macro";
+
+     lingui.t`Hello`;
+     <lingui.Trans>Hello</lingui.Trans>;
+     "##
+);
+
+to!(
+    should_not_resolve_shadowed_namespace,
+    r##"
+     import * as m from "@lingui/core/macro";
+
+     function fn(m) {
+       return m.t`Untouched`;
+     }
+     m.t`Hello`;
+     "##
+);
+
+to_panic!(
+    should_report_unknown_namespace_member,
+    Default::default(),
+    r##"
+     import * as m from "@lingui/core/macro";
+
+     m.foo(value);
+     <m.Bar />;
+     m.t`Hello`;
+     "##
+);
+
+to!(
+    js_should_transform_commonjs_require,
+    r##"
+     const path = require("path");
+     const { t, plural: i18nPlural } = require("@lingui/core/macro"), fs = require("fs");
+
+     t`Hello ${name}`;
+     i18nPlural(count, { one: "# file", other: "# files" });
+     module.exports = { path, fs };
+     "##
+);
+
+to!(
+    jsx_should_transform_commonjs_require,
+    r##"
+     const macro = require("@lingui/react/macro");
+     const { Plural } = require("@lingui/react/macro");
+
+     exports.App = function App() {
+       const { t } = macro.useLingui();
+       return <div title={t`Title`}><macro.Trans>Hello</macro.Trans><Plural value={count} one="#" other="#" /></div>;
+     };
+     "##
+);
+
+to!(
+    js_should_transform_require_in_esm,
+    r##"
+     import fs from "fs";
+     const { msg } = require("@lingui/core/macro");
+
+     export const descriptor = msg`Hello`;
+     "##
+);
@@ -0,0 +1,62 @@
+use lingui_macro::LinguiOptions;
+use serde_json::json;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+#[macro_use]
+mod common;
+
+to!(
+    inline_js_macros,
+    LinguiOptions {
+        inline_catalog: Some(Arc::new(BTreeMap::from([
+            ("OVaF9k".into(), json!(["Ahoj ", ["name"]])),
+            ("explicit.id".into(), json!(["Explicitní"])),
+            ("only.id".into(), json!(["Jen id"])),
+        ]))),
+        ..Default::default()
+    },
+    r##"
+     import { t, defineMessage, msg } from "@lingui/core/macro";
+
+     t`Hello ${name}`;
+     t({ id: "explicit.id", message: "Explicit" });
+     defineMessage({ id: "only.id" });
+     msg`Not translated ${count}`;
+     "##
+);
+
+to!(
+    inline_jsx_macros,
+    LinguiOptions {
+        inline_catalog: Some(Arc::new(BTreeMap::from([(
+            "T8kza2".into(),
+            json!(["Vítejte <0>zpět</0>"]),
+        )]))),
+        ..Default::default()
+    },
+    r##"
+     import { Trans, Plural } from "@lingui/react/macro";
+
+     <Trans>Welcome <b>back</b></Trans>;
+     <Plural value={count} one="# book" other="# books" />;
+     "##
+);
+
+to!(
+    inline_keeps_id_only_descriptor_fields,
+    LinguiOptions {
+        descriptor_fields: lingui_macro::DescriptorFields::IdOnly,
+        inline_catalog: Some(Arc::new(BTreeMap::from([(
+            "vQhkQx".into(),
+            json!(["moje zpráva"]),
+        )]))),
+        ..Default::default()
+    },
+    r##"
+     import { t } from "@lingui/core/macro";
+
+     t({ message: "my message", comment: "dropped", context: "dropped" });
+     t`my message`;
+     "##
+);
@@ -246,3 +246,38 @@ to!(
      t`Variable ${name satisfies string}`;
      "#
 );
+
+to!(
+    js_should_use_id_generation_options,
+    LinguiOptions {
+        id_generation: lingui_macro::IdGeneration {
+            algorithm: lingui_macro::HashAlgorithm::Sha512,
+            length: 10,
+            salt: Some("checkout".into()),
+            ..Default::default()
+        },
+        ..Default::default()
+    },
+    r#"
+     import { t } from '@lingui/core/macro'
+     t`Hello World`;
+     t({ message: "Hello World", context: "my context" });
+     "#
+);
+
+to!(
+    js_should_use_message_as_id,
+    LinguiOptions {
+        id_generation: lingui_macro::IdGeneration {
+            strategy: lingui_macro::IdStrategy::Message,
+            ..Default::default()
+        },
+        ..Default::default()
+    },
+    r#"
+     import { t } from '@lingui/core/macro'
+     import { Trans } from '@lingui/react/macro'
+     t`Hello ${name}`;
+     <Trans context="greeting">Hello World</Trans>;
+     "#
+);
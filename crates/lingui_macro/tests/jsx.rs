@@ -305,6 +305,26 @@ to!(
         "<Trans>\rhello\r</Trans>;\n"
     )
 );
+to_panic!(
+    jsx_nested_trans_is_an_error,
+    Default::default(),
+    r#"
+     import { Trans } from "@lingui/react/macro";
+
+     <Trans>Hello <Trans>world</Trans></Trans>;
+     "#
+);
+
+to_panic!(
+    jsx_nested_trans_in_choice_case_is_an_error,
+    Default::default(),
+    r#"
+     import { Trans, Plural } from "@lingui/react/macro";
+
+     <Plural value={count} one={<b>One <Trans>item</Trans></b>} other="Many" />;
+     "#
+);
+
 //   {
 //     name: "production - import_type_doesn't_interference_on_normal_import",
 //     production: true,
@@ -319,3 +339,29 @@ to!(
 //         <Trans id="msg.hello" />;
 //       `,
 //   },
+
+to_panic!(
+    jsx_text_in_placeholder_is_reported,
+    Default::default(),
+    r#"
+     import { Trans } from "@lingui/react/macro";
+
+     <Trans>
+       Note {flag && <b>Important</b>}
+       <ul>{items.map((item) => <li key={item}>Item {item}</li>)}</ul>
+       {name || "Anonymous"} {cn("a", "b")}
+     </Trans>;
+     "#
+);
+
+to!(
+    jsx_macros_in_placeholder_are_not_reported,
+    r#"
+     import { Trans } from "@lingui/react/macro";
+     import { t } from "@lingui/core/macro";
+
+     <Trans>
+       Note {flag && <Trans>Important</Trans>} {items.map((item) => t`Item ${item}`)}
+     </Trans>;
+     "#
+);
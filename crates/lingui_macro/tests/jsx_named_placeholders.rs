@@ -310,3 +310,48 @@ import { Trans } from '@lingui/react/macro';
 <Trans><a _t href="/">click</a></Trans>
      "#
 );
+
+to!(
+    defaults_member_and_namespaced_names,
+    LinguiOptions {
+        jsx_placeholder_defaults: Some(std::collections::HashMap::from([
+            ("Icons.Star".into(), "star".into()),
+            ("motion.div".into(), "box".into()),
+            ("svg:title".into(), "title".into()),
+        ])),
+        ..Default::default()
+    },
+    r#"
+import { Trans } from "@lingui/react/macro";
+<Trans>
+  Rated <Icons.Star /> in a <motion.div layout>box</motion.div> with <svg:title>a title</svg:title>.
+</Trans>;
+     "#
+);
+
+to!(
+    deduplication_identical_member_names,
+    LinguiOptions {
+        jsx_placeholder_defaults: Some(std::collections::HashMap::from([(
+            "Icons.Star".into(),
+            "star".into()
+        ),])),
+        ..Default::default()
+    },
+    r#"
+import { Trans } from "@lingui/react/macro";
+<Trans><Icons.Star /> and another <Icons.Star /></Trans>;
+     "#
+);
+
+to_panic!(
+    deduplication_different_member_names,
+    LinguiOptions {
+        jsx_placeholder_attribute: Some("_t".into()),
+        ..Default::default()
+    },
+    r#"
+import { Trans } from "@lingui/react/macro";
+<Trans><Icons.Star _t="icon" /> and <Icons.Heart _t="icon" /></Trans>;
+     "#
+);
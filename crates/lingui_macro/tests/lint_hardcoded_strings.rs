@@ -0,0 +1,59 @@
+use lingui_macro::LinguiOptions;
+
+#[macro_use]
+mod common;
+
+to_panic!(
+    lint_reports_text_and_attributes,
+    LinguiOptions {
+        lint_hardcoded_strings: true,
+        ..Default::default()
+    },
+    r#"
+     import { Trans } from "@lingui/react/macro";
+
+     <div title="Settings" className="panel">
+       <h1>
+         Settings
+       </h1>
+       <input placeholder="Search…" aria-label={"Search"} type="search" />
+       <p>{count} / {total}</p>
+       <Trans>Saved <img alt="Checkmark" /></Trans>
+     </div>;
+     "#
+);
+
+to!(
+    lint_accepts_translated_strings,
+    LinguiOptions {
+        lint_hardcoded_strings: true,
+        ..Default::default()
+    },
+    r##"
+     import { Trans, useLingui } from "@lingui/react/macro";
+     import * as m from "@lingui/react/macro";
+
+     function Search() {
+       const { t } = useLingui();
+       return (
+         <>
+           <input placeholder={t`Search…`} type="search" />
+           <m.Trans>Search results</m.Trans>
+           <m.Plural value={count} one="# result" other="# results" />
+           <span>{" – "}</span>
+         </>
+       );
+     }
+     "##
+);
+
+to!(
+    lint_skips_files_without_macros,
+    LinguiOptions {
+        lint_hardcoded_strings: true,
+        ..Default::default()
+    },
+    r#"
+     <img alt="Logo" />;
+     "#
+);
@@ -0,0 +1,86 @@
+use lingui_macro::LinguiOptions;
+
+#[macro_use]
+mod common;
+
+to_panic!(
+    js_plural_cases_not_an_object,
+    LinguiOptions::default(),
+    r#"
+     import { t, plural } from "@lingui/core/macro";
+
+     t`${plural(count, cases)}`;
+     "#
+);
+
+to_panic!(
+    js_plural_spread_in_cases,
+    LinguiOptions::default(),
+    r##"
+     import { plural } from "@lingui/core/macro";
+
+     plural(count, { ...cases, other: "# books" });
+     "##
+);
+
+to_panic!(
+    js_plural_shorthand_case,
+    LinguiOptions::default(),
+    r##"
+     import { plural } from "@lingui/core/macro";
+
+     plural(count, { one, other: "# books" });
+     "##
+);
+
+to_panic!(
+    js_plural_computed_case_key,
+    LinguiOptions::default(),
+    r##"
+     import { plural } from "@lingui/core/macro";
+
+     plural(count, { [category]: "# book", other: "# books" });
+     "##
+);
+
+to_panic!(
+    js_plural_non_numeric_offset,
+    LinguiOptions::default(),
+    r##"
+     import { plural } from "@lingui/core/macro";
+
+     plural(count, { offset: skipped, other: "# books" });
+     "##
+);
+
+to_panic!(
+    jsx_plural_non_numeric_offset,
+    LinguiOptions::default(),
+    r##"
+     import { Plural } from "@lingui/react/macro";
+
+     <Plural value={count} offset="one" other="# books" />;
+     "##
+);
+
+to_panic!(
+    jsx_plural_unsupported_case_value,
+    LinguiOptions::default(),
+    r##"
+     import { Plural } from "@lingui/react/macro";
+
+     <Plural value={count} one=<b># book</b> other="# books" />;
+     "##
+);
+
+to_panic!(
+    jsx_plural_with_children,
+    LinguiOptions::default(),
+    r##"
+     import { Trans, Plural } from "@lingui/react/macro";
+
+     <Trans>
+       You have <Plural value={count} other="# books">books</Plural>
+     </Trans>;
+     "##
+);
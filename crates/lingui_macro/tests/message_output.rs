@@ -0,0 +1,48 @@
+use lingui_macro::{DescriptorFields, LinguiOptions, MessageOutput};
+
+#[macro_use]
+mod common;
+
+to!(
+    compiled_js_macros,
+    LinguiOptions {
+        message_output: MessageOutput::Compiled,
+        ..Default::default()
+    },
+    r##"
+     import { t, msg, plural } from "@lingui/core/macro";
+
+     t`Hello ${name}`;
+     t({ id: "explicit.id", message: "{count, plural, one {# book} other {# books}}" });
+     msg({ id: "only.id" });
+     t`You have ${plural(count, { one: "# message", other: "# messages" })}`;
+     "##
+);
+
+to!(
+    compiled_jsx_macros,
+    LinguiOptions {
+        message_output: MessageOutput::Compiled,
+        ..Default::default()
+    },
+    r##"
+     import { Trans, Plural } from "@lingui/react/macro";
+
+     <Trans>Read <a href="/docs">the docs</a>, {name}</Trans>;
+     <Plural value={count} one="# book" other="# books" />;
+     "##
+);
+
+to!(
+    compiled_messages_follow_descriptor_fields,
+    LinguiOptions {
+        descriptor_fields: DescriptorFields::IdOnly,
+        message_output: MessageOutput::Compiled,
+        ..Default::default()
+    },
+    r#"
+     import { t } from "@lingui/core/macro";
+
+     t`Hello ${name}`;
+     "#
+);
@@ -0,0 +1,38 @@
+use lingui_macro::LinguiOptions;
+
+#[macro_use]
+mod common;
+
+to!(
+    origin_in_js_macros,
+    LinguiOptions {
+        include_origin: true,
+        ..Default::default()
+    },
+    r##"
+     import { t, defineMessage } from "@lingui/core/macro";
+
+     const a = t`Hello ${name}`;
+     const b = defineMessage({ id: "explicit", message: "Explicit" });
+     "##
+);
+
+to!(
+    origin_in_jsx_macros,
+    LinguiOptions {
+        include_origin: true,
+        ..Default::default()
+    },
+    r##"
+     import { Trans, Plural } from "@lingui/react/macro";
+
+     function App() {
+       return (
+         <div>
+           <Trans>Hello</Trans>
+           <Plural value={count} one="# book" other="# books" />
+         </div>
+       );
+     }
+     "##
+);
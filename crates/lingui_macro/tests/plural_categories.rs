@@ -0,0 +1,83 @@
+use lingui_macro::LinguiOptions;
+
+#[macro_use]
+mod common;
+
+to_panic!(
+    warns_about_unused_cardinal_category,
+    LinguiOptions {
+        source_locale: Some("en".into()),
+        ..Default::default()
+    },
+    r##"
+     import { plural } from "@lingui/core/macro";
+
+     plural(count, { one: "# book", few: "# books", other: "# books" });
+     "##
+);
+
+to_panic!(
+    warns_about_unused_category_in_jsx,
+    LinguiOptions {
+        source_locale: Some("en-US".into()),
+        ..Default::default()
+    },
+    r##"
+     import { Plural } from "@lingui/react/macro";
+
+     <Plural value={count} one="# book" many="# books" other="# books" />;
+     "##
+);
+
+to_panic!(
+    warns_about_unused_category_in_raw_message,
+    LinguiOptions {
+        source_locale: Some("de".into()),
+        ..Default::default()
+    },
+    r##"
+     import { defineMessage } from "@lingui/core/macro";
+
+     defineMessage({ message: "{count, selectordinal, one {#.} other {#.}}" });
+     "##
+);
+
+to!(
+    accepts_categories_of_source_locale,
+    LinguiOptions {
+        source_locale: Some("cs".into()),
+        ..Default::default()
+    },
+    r##"
+     import { plural, selectOrdinal } from "@lingui/core/macro";
+
+     plural(count, { 0: "none", one: "# kniha", few: "# knihy", many: "# knihy", other: "# knih" });
+     selectOrdinal(count, { other: "#." });
+     "##
+);
+
+to!(
+    ignores_unknown_locales,
+    LinguiOptions {
+        source_locale: Some("xx".into()),
+        ..Default::default()
+    },
+    r##"
+     import { plural } from "@lingui/core/macro";
+
+     plural(count, { few: "# books", other: "# books" });
+     "##
+);
+
+to_panic!(
+    reports_missing_other_category,
+    LinguiOptions {
+        source_locale: Some("en".into()),
+        ..Default::default()
+    },
+    r##"
+     import { Plural } from "@lingui/react/macro";
+
+     <Plural value={count} one="# book" />;
+     "##
+);
@@ -0,0 +1,62 @@
+use lingui_macro::LinguiOptions;
+
+#[macro_use]
+mod common;
+
+to!(
+    pseudo_js_macros,
+    LinguiOptions {
+        pseudo_localize: true,
+        ..Default::default()
+    },
+    r##"
+     import { t, defineMessage, plural } from "@lingui/core/macro";
+
+     t`Hello ${name}`;
+     t({ id: "explicit.id", message: "{count, plural, one {# book} other {# books}}" });
+     defineMessage({ id: "only.id" });
+     t`You have ${plural(count, { one: "# message", other: "# messages" })}`;
+     "##
+);
+
+to!(
+    pseudo_jsx_macros,
+    LinguiOptions {
+        pseudo_localize: true,
+        ..Default::default()
+    },
+    r##"
+     import { Trans, Select } from "@lingui/react/macro";
+
+     <Trans>Read <a href="/docs">the docs</a> {name}<br/></Trans>;
+     <Select value={gender} _male="He" other="They" />;
+     "##
+);
+
+to!(
+    pseudo_keeps_named_placeholders,
+    LinguiOptions {
+        pseudo_localize: true,
+        jsx_placeholder_attribute: Some("_t".into()),
+        ..Default::default()
+    },
+    r##"
+     import { Trans } from "@lingui/react/macro";
+
+     <Trans>Read <a _t="help-link" href="/help">the help</a></Trans>;
+     "##
+);
+
+to!(
+    pseudo_keeps_message_with_id_only_descriptor_fields,
+    LinguiOptions {
+        descriptor_fields: lingui_macro::DescriptorFields::IdOnly,
+        pseudo_localize: true,
+        ..Default::default()
+    },
+    r##"
+     import { t } from "@lingui/core/macro";
+
+     t`my message`;
+     "##
+);
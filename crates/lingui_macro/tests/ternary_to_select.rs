@@ -0,0 +1,55 @@
+use lingui_macro::LinguiOptions;
+
+#[macro_use]
+mod common;
+
+to!(
+    ternary_jsx_branches,
+    LinguiOptions {
+        ternary_to_select: true,
+        ..Default::default()
+    },
+    r#"
+     import { Trans } from "@lingui/react/macro";
+
+     <Trans>You are {isAdmin ? "an administrator" : <b>a member of {team}</b>}.</Trans>;
+     <Trans>{count > 1 ? <>Many {things}</> : "One"} and {user.name}</Trans>;
+     "#
+);
+
+to!(
+    ternary_in_template,
+    LinguiOptions {
+        ternary_to_select: true,
+        ..Default::default()
+    },
+    r#"
+     import { t } from "@lingui/core/macro";
+
+     t`Saved ${count} ${count === 1 ? "file" : `files in ${folder}`}`;
+     t`Welcome${user.isNew ? " aboard" : " back"}, ${user.isVip ? vip ? "dear" : "valued" : ""} customer`;
+     "#
+);
+
+to_panic!(
+    ternary_text_is_reported_when_disabled,
+    Default::default(),
+    r#"
+     import { Trans } from "@lingui/react/macro";
+
+     <Trans>You are {isAdmin ? "an administrator" : "a member"}.</Trans>;
+     "#
+);
+
+to_panic!(
+    ternary_with_unsupported_branch_is_reported,
+    LinguiOptions {
+        ternary_to_select: true,
+        ..Default::default()
+    },
+    r#"
+     import { t } from "@lingui/core/macro";
+
+     t`Role: ${isAdmin ? "administrator" : user.role} and ${a ? b : c}`;
+     "#
+);
@@ -27,6 +27,9 @@ describe("linguiMacroSwcPlugin", () => {
             "jsxPlaceholderDefaults": {
               "a": "link",
             },
+            "locales": [
+              "en",
+            ],
             "runtimeModules": {
               "Trans": [
                 "@acme/react",
@@ -41,6 +44,7 @@ describe("linguiMacroSwcPlugin", () => {
                 "useLingui",
               ],
             },
+            "sourceLocale": "en",
           },
         ]
       `)
@@ -67,6 +71,9 @@ describe("linguiMacroSwcPlugin", () => {
             "a": "anchor",
             "strong": "bold",
           },
+          "locales": [
+            "en",
+          ],
           "runtimeModules": {
             "Trans": [
               "@custom/react",
@@ -81,6 +88,7 @@ describe("linguiMacroSwcPlugin", () => {
               "useCustomLingui",
             ],
           },
+          "sourceLocale": "en",
         },
       ]
     `)
@@ -115,6 +123,9 @@ describe("linguiMacroSwcPlugin", () => {
             "a": "anchor",
             "strong": "bold",
           },
+          "locales": [
+            "en",
+          ],
           "runtimeModules": {
             "Trans": [
               "@override/react",
@@ -129,6 +140,7 @@ describe("linguiMacroSwcPlugin", () => {
               "useCustomLingui",
             ],
           },
+          "sourceLocale": "en",
         },
       ]
     `)
@@ -171,6 +183,9 @@ describe('mapOptions', () => {
           "a": "anchor",
           "strong": "bold",
         },
+        "locales": [
+          "en",
+        ],
         "runtimeModules": {
           "Trans": [
             "@custom/react",
@@ -185,6 +200,7 @@ describe('mapOptions', () => {
             "useCustomLingui",
           ],
         },
+        "sourceLocale": "en",
       }
     `);
   })
@@ -44,6 +44,48 @@ export type LinguiMacroOptions = {
    * - `true` — `/** i18n *\/` (requires `@lingui/cli` >= 6.4.0).
    */
   useJsdocI18nComment?: boolean
+  /**
+   * Compiled messages of a single locale (the `messages` object produced by `lingui compile`).
+   * Descriptors are emitted with the compiled translation as `message`, so no runtime catalog is needed.
+   * Messages missing from the catalog fall back to the compiled source message.
+   */
+  inlineCatalog?: Record<string, unknown>
+  /** Locales of the project. The first one is used as `sourceLocale` when that is not set. */
+  locales?: string[]
+  /** Locale of the source messages. Plural cases are checked against the CLDR plural categories of this locale. */
+  sourceLocale?: string
+  /** Emit messages pseudo-localized (accented, padded and bracketed text) for QA builds. */
+  pseudoLocalize?: boolean
+  /** How ids are generated for messages without an explicit `id`. */
+  idGeneration?: {
+    strategy?: "hash" | "message"
+    algorithm?: "sha256" | "sha512"
+    length?: number
+    salt?: string
+  }
+  /** Add `origin: [file, line, column]` to generated descriptors. Ignored in production. */
+  includeOrigin?: boolean
+  /** Functions of user packages transformed like the built-in `t` or `msg` macros. */
+  customMacros?: {
+    name: string
+    package: string
+    kind?: "t" | "msg"
+    args: Array<
+      "message" | "context" | "comment" | "id" | "count" | "zero" | "one" | "two" | "few" | "many" | "other"
+    >
+  }[]
+  /** Turn ternaries with string, template or JSX branches inside messages into an ICU `select`. */
+  ternaryToSelect?: boolean
+  /** Warn about JSX text and `alt`, `title`, `placeholder` or `aria-label` attributes outside of macros. */
+  lintHardcodedStrings?: boolean
+  /** Report diagnostics as text with a code frame, or as one line of JSON each. */
+  diagnosticsFormat?: "text" | "json"
+  /** Report all warnings as errors. */
+  strict?: boolean
+  /** Severity per diagnostic, keyed by code (`LINGUI013`) or name (`unsupported-spread`). */
+  diagnosticSeverity?: Record<string, "error" | "warning" | "off">
+  /** Emit descriptor messages as ICU strings, or precompiled for the runtime. */
+  messageOutput?: "icu" | "compiled"
 }
 
 /** Makes all properties in `T` optional, recursing into nested objects but preserving tuples/arrays as-is. */
@@ -65,6 +107,8 @@ export function mapOptions(linguiConfig: LinguiConfigNormalized, overrides?: Dee
     jsxPlaceholderAttribute: linguiConfig.macro.jsxPlaceholderAttribute,
     jsxPlaceholderDefaults: linguiConfig.macro.jsxPlaceholderDefaults,
     idPrefixLeader: linguiConfig.macro.idPrefixLeader,
+    locales: linguiConfig.locales,
+    sourceLocale: linguiConfig.sourceLocale || undefined,
     ...overrides,
     runtimeModules: {
       ...linguiConfig.runtimeConfigModule,